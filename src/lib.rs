@@ -16,16 +16,59 @@
 //!
 //! Menus can include a title, footer message, and any combination of [8-bit](https://en.wikipedia.org/wiki/ANSI_escape_code#8-bit)
 //! colored backgrounds and text by configuring `MenuProps`. Menus that don't fit the console window are paginated.
+//! Terminals should be at least 8 rows tall. Smaller terminals still show one option per page, but the title and
+//! message may be cut off.
+//!
+//! ```
+//! # use console_menu::{Menu, MenuOption, MenuProps};
+//! let options = (1..=10).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+//! let mut menu = Menu::new(options, MenuProps { title: "Numbers", ..MenuProps::default() });
+//! menu.render_to_string(80, 3);
+//! assert_eq!(menu.options_per_page(), 1);
+//! ```
 //!
 //! Menu controls are as follows:
 //! 
 //! | Key Bind | Action      |
 //! | -------- | ----------- |
 //! | ↓, ↑, ←, →, h, j, k, l | make selection        |
+//! | home, end | jump to first / last option |
+//! | page up, page down | change page |
 //! | enter    | confirm     |
 //! | esc, q   | exit        |
+//! | /        | search      |
+//!
+//! Typing a character that isn't bound to anything jumps to the next option starting with it.
+//! Key bindings can be customized through `MenuProps::key_bindings`. Pressing Ctrl+C restores
+//! the terminal and exits the process with status 130, as it would be without a menu open.
 
-use console::{Key, Term};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+pub use console::{Key, Term};
+use unicode_width::UnicodeWidthChar;
+use terminal::ColorSupport;
+
+/// Number of spaces between columns of options.
+const COLUMN_GAP: usize = 2;
+/// Number of columns taken up by the scrollbar and the space before it.
+const SCROLLBAR_WIDTH: usize = 2;
+/// Shown in place of the message while an action runs with `show_loading` set.
+const LOADING_MESSAGE: &str = "Running…";
+/// The characters of the loading indicator, in the order they're shown.
+const SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+/// Time between frames of the loading indicator.
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+/// Time between checks of the terminal size while waiting for a key.
+const RESIZE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Moves the cursor to the top left and clears the screen.
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[J\x1b[H";
 
 /// A collection of pre-selected color values to simplify menu theming.
 pub mod color {
@@ -40,355 +83,5785 @@ pub mod color {
     pub const YELLOW: u8 = 220;
     pub const BLACK: u8 = 233;
     pub const DARK_GRAY:u8 = 236;
-}
+    pub const CYAN: u8 = 51;
+    pub const MAGENTA: u8 = 201;
+    pub const LIME: u8 = 46;
+    pub const MAROON: u8 = 88;
+    pub const NAVY: u8 = 18;
+    pub const TEAL: u8 = 30;
+    pub const SILVER: u8 = 250;
+    pub const GOLD: u8 = 178;
+    pub const PINK: u8 = 218;
+    pub const VIOLET: u8 = 177;
+    pub const INDIGO: u8 = 54;
+    pub const BROWN: u8 = 94;
+    pub const TURQUOISE: u8 = 44;
+    pub const CORAL: u8 = 210;
+    pub const SALMON: u8 = 209;
 
-/// Stores configuration data passed to a `Menu` on creation.
-///
-/// Menus use [8-bit](https://en.wikipedia.org/wiki/ANSI_escape_code#8-bit) colors to ensure
-/// widespread terminal support. It should be noted that values from 0-15 will make colors vary
-/// based on individual terminal settings.
-///
-/// Configure a subset of properties using the defaults and struct update syntax:
-/// ```
-/// # use console_menu::MenuProps;
-/// let props = MenuProps {
-///     title: "My Menu",
-///     ..MenuProps::default()
-/// };
-/// ```
-pub struct MenuProps<'a> {
-    /// Displays above the list of menu options. Pass an empty string for no title.
-    pub title: &'a str,
-    /// Display below the list of menu options. Pass an empty string for no message.
-    pub message: &'a str,
-    /// If true, menu will exit immediately upon an option being selected.
-    pub exit_on_action: bool,
-    /// The background color for the menu.
-    pub bg_color: u8,
-    /// The foreground (text) color for the menu.
-    pub fg_color: u8,
-    /// Optional color for the title. If None, the foreground color will be used.
-    pub title_color: Option<u8>,
-    /// Optional color for the selected menu option. If None, the foreground color will be used.
-    pub selected_color: Option<u8>,
-    /// Optional color for the footer message. If None, the foreground color will be used.
-    pub msg_color: Option<u8>,
+    /// The named constants above, looked up by `name` and `by_name`.
+    const NAMES: [(&str, u8); 26] = [
+        ("WHITE", WHITE), ("LIGHT_GRAY", LIGHT_GRAY), ("GRAY", GRAY), ("BLUE", BLUE),
+        ("GREEN", GREEN), ("PURPLE", PURPLE), ("RED", RED), ("ORANGE", ORANGE),
+        ("YELLOW", YELLOW), ("BLACK", BLACK), ("DARK_GRAY", DARK_GRAY), ("CYAN", CYAN),
+        ("MAGENTA", MAGENTA), ("LIME", LIME), ("MAROON", MAROON), ("NAVY", NAVY),
+        ("TEAL", TEAL), ("SILVER", SILVER), ("GOLD", GOLD), ("PINK", PINK),
+        ("VIOLET", VIOLET), ("INDIGO", INDIGO), ("BROWN", BROWN), ("TURQUOISE", TURQUOISE),
+        ("CORAL", CORAL), ("SALMON", SALMON),
+    ];
+
+    /// Standard xterm values for the 16 system colors. Actual colors depend on terminal settings.
+    const SYSTEM_COLORS: [(u8, u8, u8); 16] = [
+        (0, 0, 0), (128, 0, 0), (0, 128, 0), (128, 128, 0),
+        (0, 0, 128), (128, 0, 128), (0, 128, 128), (192, 192, 192),
+        (128, 128, 128), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+        (0, 0, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+    ];
+
+    /// Intensity of each step of the 6x6x6 color cube at 16-231.
+    const CUBE_STEPS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    /// Returns the red, green and blue components of an 8-bit color, following the xterm palette.
+    ///
+    /// ```
+    /// # use console_menu::color;
+    /// assert_eq!(color::to_rgb(color::RED), (215, 0, 0));
+    /// assert_eq!(color::to_rgb(232), (8, 8, 8));
+    /// ```
+    pub fn to_rgb(color: u8) -> (u8, u8, u8) {
+        match color {
+            0..=15 => SYSTEM_COLORS[color as usize],
+            16..=231 => {
+                let i = color as usize - 16;
+                (CUBE_STEPS[i / 36], CUBE_STEPS[i / 6 % 6], CUBE_STEPS[i % 6])
+            }
+            _ => {
+                let level = 8 + (color - 232) * 10;
+                (level, level, level)
+            }
+        }
+    }
+
+    /// Returns the name of the constant in this module with the value `color`, if there is one.
+    ///
+    /// ```
+    /// # use console_menu::color;
+    /// assert_eq!(color::name(color::RED), Some("RED"));
+    /// assert_eq!(color::name(color::DARK_GRAY), Some("DARK_GRAY"));
+    /// assert_eq!(color::name(17), None);
+    ///
+    /// for value in [color::CYAN, color::GOLD, color::SALMON, color::WHITE] {
+    ///     assert_eq!(color::by_name(color::name(value).unwrap()), Some(value));
+    /// }
+    /// ```
+    pub fn name(color: u8) -> Option<&'static str> {
+        NAMES.iter().find(|&&(_, value)| value == color).map(|&(name, _)| name)
+    }
+
+    /// Returns the value of the constant in this module called `name`, ignoring case.
+    ///
+    /// ```
+    /// # use console_menu::color;
+    /// assert_eq!(color::by_name("RED"), Some(color::RED));
+    /// assert_eq!(color::by_name("light_gray"), Some(color::LIGHT_GRAY));
+    /// assert_eq!(color::by_name("chartreuse"), None);
+    /// ```
+    pub fn by_name(name: &str) -> Option<u8> {
+        NAMES.iter().find(|(constant, _)| constant.eq_ignore_ascii_case(name)).map(|&(_, value)| value)
+    }
+
+    /// The color in `candidates` closest to `rgb` by euclidean distance.
+    pub(crate) fn nearest(rgb: (u8, u8, u8), candidates: std::ops::RangeInclusive<u8>) -> u8 {
+        let distance = |color: u8| {
+            let (r, g, b) = to_rgb(color);
+            let dr = r as i32 - rgb.0 as i32;
+            let dg = g as i32 - rgb.1 as i32;
+            let db = b as i32 - rgb.2 as i32;
+            dr * dr + dg * dg + db * db
+        };
+        candidates.min_by_key(|&color| distance(color)).unwrap_or(0)
+    }
+
+    /// Returns the 8-bit color closest to the given red, green and blue components, from the color
+    /// cube and grayscale ramp at 16-255. The system colors are skipped as they vary between
+    /// terminals.
+    ///
+    /// ```
+    /// # use console_menu::color;
+    /// assert_eq!(color::rgb_to_256(0, 0, 0), 16);
+    /// assert_eq!(color::rgb_to_256(255, 0, 0), 196);
+    /// assert_eq!(color::rgb_to_256(250, 5, 10), 196);
+    /// assert_eq!(color::rgb_to_256(128, 128, 128), 244);
+    /// ```
+    pub fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+        nearest((r, g, b), 16..=255)
+    }
+
+    /// Returns the 8-bit color closest to a web color written as `#RRGGBB` or `RRGGBB`.
+    ///
+    /// ```
+    /// # use console_menu::color::{self, ColorError};
+    /// assert_eq!(color::hex_to_256("FF0000"), Ok(color::rgb_to_256(255, 0, 0)));
+    /// assert_eq!(color::hex_to_256("#0087d7"), Ok(32));
+    /// assert_eq!(color::hex_to_256("#fff"), Err(ColorError::InvalidHex("#fff".to_owned())));
+    /// ```
+    pub fn hex_to_256(hex: &str) -> Result<u8, ColorError> {
+        let invalid = || ColorError::InvalidHex(hex.to_owned());
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let component = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| invalid());
+        Ok(rgb_to_256(component(0)?, component(2)?, component(4)?))
+    }
+
+    /// Errors produced when parsing a color.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum ColorError {
+        /// The text isn't a hex color of the form `#RRGGBB` or `RRGGBB`.
+        InvalidHex(String),
+    }
+
+    impl std::fmt::Display for ColorError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ColorError::InvalidHex(hex) => write!(f, "{:?} is not a hex color", hex),
+            }
+        }
+    }
+
+    impl std::error::Error for ColorError {}
 }
 
-/// ```
-/// # use console_menu::MenuProps;
-/// # fn default() -> MenuProps<'static> {
-/// MenuProps {
-///     title: "",
-///     message: "",
-///     exit_on_action: true,
-///     bg_color: 8,
-///     fg_color: 15,
-///     title_color: None,
-///     selected_color: None,
-///     msg_color: Some(7),
-/// }
-/// # }
-/// ```
-impl Default for MenuProps<'_> {
-    fn default() -> MenuProps<'static> {
-        MenuProps {
-            title: "",
-            message: "",
-            exit_on_action: true,
-            bg_color: 8,
-            fg_color: 15,
-            title_color: None,
-            selected_color: None,
-            msg_color: Some(7),
+/// Detection of the colors supported by the terminal.
+pub mod terminal {
+    use std::env;
+
+    /// The range of colors a terminal can display, from least to most capable.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum ColorSupport {
+        /// No colors at all. Menus are drawn as plain text.
+        Monochrome,
+        /// The 16 system colors.
+        Color16,
+        /// The 8-bit palette.
+        Color256,
+        /// 24-bit colors.
+        TrueColor,
+    }
+
+    /// Returns true if the terminal advertises support for 24-bit colors.
+    pub fn supports_truecolor() -> bool {
+        color_support() == ColorSupport::TrueColor
+    }
+
+    /// Guesses the colors supported by the terminal from environment variables.
+    ///
+    /// `COLORTERM` set to `truecolor` or `24bit` indicates 24-bit colors, as do terminals known
+    /// to support them through `TERM_PROGRAM` or `VTE_VERSION`. Otherwise `TERM` is checked for
+    /// terminals with fewer colors. When nothing conclusive is found, `Color256` is assumed.
+    ///
+    /// ```
+    /// # use console_menu::terminal::{self, ColorSupport};
+    /// # for var in ["TERM_PROGRAM", "VTE_VERSION"] { std::env::remove_var(var) }
+    /// std::env::set_var("COLORTERM", "truecolor");
+    /// assert_eq!(terminal::color_support(), ColorSupport::TrueColor);
+    ///
+    /// std::env::remove_var("COLORTERM");
+    /// std::env::set_var("TERM", "xterm-256color");
+    /// assert_eq!(terminal::color_support(), ColorSupport::Color256);
+    ///
+    /// std::env::set_var("TERM", "linux");
+    /// assert_eq!(terminal::color_support(), ColorSupport::Color16);
+    ///
+    /// std::env::set_var("TERM", "dumb");
+    /// assert_eq!(terminal::color_support(), ColorSupport::Monochrome);
+    /// ```
+    pub fn color_support() -> ColorSupport {
+        let var = |name: &str| env::var(name).unwrap_or_default();
+
+        let colorterm = var("COLORTERM").to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorSupport::TrueColor;
+        }
+        if matches!(var("TERM_PROGRAM").as_str(), "iTerm.app" | "WezTerm" | "vscode" | "Hyper") {
+            return ColorSupport::TrueColor;
+        }
+        // VTE based terminals support 24-bit colors since 0.36
+        if var("VTE_VERSION").parse::<u32>().is_ok_and(|version| version >= 3600) {
+            return ColorSupport::TrueColor;
+        }
+
+        let term = var("TERM");
+        if term == "dumb" {
+            ColorSupport::Monochrome
+        } else if term == "linux" || term == "ansi" || term.ends_with("-16color") {
+            ColorSupport::Color16
+        } else {
+            ColorSupport::Color256
         }
     }
 }
 
-/// An element in a `Menu`.
-///
-/// Consists of a label and a callback. Callbacks can be any function, including functions that
-/// call nested menus:
+/// Complete color schemes that can be applied to `MenuProps`.
 ///
 /// ```
-/// # use console_menu::{Menu, MenuOption, MenuProps};
-/// let mut nested_menu = Menu::new(vec![], MenuProps::default());
-/// let show_nested = MenuOption::new("show nested menu", move || nested_menu.show());
+/// # use console_menu::{theme, MenuProps};
+/// let props = MenuProps {
+///     title: "My Menu",
+///     ..MenuProps::with_theme(&theme::OCEAN)
+/// };
 /// ```
+pub mod theme {
+    use crate::{color, ColorMode, MenuProps};
 
-pub struct MenuOption {
-    pub label: String,
-    pub action: Box<dyn FnMut()>,
-}
+    /// The colors used by a menu, mirroring the color fields of `MenuProps`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Theme {
+        pub bg_color: ColorMode,
+        pub fg_color: ColorMode,
+        pub title_color: Option<ColorMode>,
+        pub selected_color: Option<ColorMode>,
+        pub msg_color: Option<ColorMode>,
+    }
 
-impl MenuOption {
-    pub fn new(label: &str, action: impl FnMut() + 'static) -> Self {
-        Self {
-            label: label.to_owned(),
-            action: Box::new(action),
+    impl Theme {
+        /// Creates `MenuProps` with the given title and message, using this theme's colors and
+        /// the default values for everything else.
+        pub fn to_menu_props<'a>(self, title: &'a str, message: &'a str) -> MenuProps<'a> {
+            MenuProps {
+                title,
+                message,
+                ..MenuProps::with_theme(&self)
+            }
         }
     }
+
+    /// The colors of `MenuProps::default()`.
+    pub const DEFAULT: Theme = Theme {
+        bg_color: ColorMode::Color256(color::GRAY),
+        fg_color: ColorMode::Color256(color::WHITE),
+        title_color: None,
+        selected_color: None,
+        msg_color: Some(ColorMode::Color256(color::LIGHT_GRAY)),
+    };
+
+    pub const OCEAN: Theme = Theme {
+        bg_color: ColorMode::Color256(17),
+        fg_color: ColorMode::Color256(153),
+        title_color: Some(ColorMode::Color256(51)),
+        selected_color: Some(ColorMode::Color256(color::WHITE)),
+        msg_color: Some(ColorMode::Color256(67)),
+    };
+
+    pub const FOREST: Theme = Theme {
+        bg_color: ColorMode::Color256(22),
+        fg_color: ColorMode::Color256(194),
+        title_color: Some(ColorMode::Color256(154)),
+        selected_color: Some(ColorMode::Color256(color::WHITE)),
+        msg_color: Some(ColorMode::Color256(108)),
+    };
+
+    pub const SUNSET: Theme = Theme {
+        bg_color: ColorMode::Color256(52),
+        fg_color: ColorMode::Color256(223),
+        title_color: Some(ColorMode::Color256(color::ORANGE)),
+        selected_color: Some(ColorMode::Color256(color::YELLOW)),
+        msg_color: Some(ColorMode::Color256(174)),
+    };
+
+    pub const MONOKAI: Theme = Theme {
+        bg_color: ColorMode::Color256(235),
+        fg_color: ColorMode::Color256(231),
+        title_color: Some(ColorMode::Color256(197)),
+        selected_color: Some(ColorMode::Color256(148)),
+        msg_color: Some(ColorMode::Color256(141)),
+    };
+
+    pub const HIGH_CONTRAST: Theme = Theme {
+        bg_color: ColorMode::Color256(16),
+        fg_color: ColorMode::Color256(231),
+        title_color: None,
+        selected_color: Some(ColorMode::Color256(226)),
+        msg_color: None,
+    };
 }
 
-/// ```
-/// # use console_menu::MenuOption;
-/// # fn default() -> MenuOption {
-/// MenuOption::new("exit", || {})
-/// # }
-/// ```
-impl Default for MenuOption {
-    fn default() -> MenuOption {
-        MenuOption::new("exit", || {})
-    }
+/// ANSI escape sequences for the text styles used when rendering menus.
+pub mod ansi {
+    pub const RESET: &str = "\x1b[0m";
+    pub const BOLD_ON: &str = "\x1b[1m";
+    pub const BOLD_OFF: &str = "\x1b[22m";
+    pub const UNDERLINE_ON: &str = "\x1b[4m";
+    pub const UNDERLINE_OFF: &str = "\x1b[24m";
+    pub const DIM_ON: &str = "\x1b[2m";
+    pub const DIM_OFF: &str = "\x1b[22m";
+    pub const ITALIC_ON: &str = "\x1b[3m";
+    pub const ITALIC_OFF: &str = "\x1b[23m";
+    pub const STRIKETHROUGH_ON: &str = "\x1b[9m";
+    pub const STRIKETHROUGH_OFF: &str = "\x1b[29m";
 }
 
-/// Interactive console menu.
+/// A scripted terminal for testing menus.
 ///
-/// Create a menu by passing it a list of `MenuOption` and a `MenuProps`. Display using`.show()`.
+/// ```
+/// use console_menu::{Key, Menu, MenuOption, MenuProps};
+/// use console_menu::testing::MockTerm;
 ///
-/// ```no_run
-/// # use console_menu::{Menu, MenuOption, MenuProps};
-/// let menu_options = vec![
-///     MenuOption::new("option 1", || println!("option one!")),
-///     MenuOption::new("option 2", || println!("option two!")),
-///     MenuOption::new("option 3", || println!("option three!")),
-/// ];
-/// let mut menu = Menu::new(menu_options, MenuProps::default());
-/// menu.show();
+/// let term = MockTerm::new(vec![Key::ArrowDown, Key::Enter]);
+/// let mut menu = Menu::new(vec![
+///     MenuOption::with_value("tea", 'T'),
+///     MenuOption::with_value("coffee", 'C'),
+/// ], MenuProps { title: "Drinks", ..MenuProps::default() });
+///
+/// assert_eq!(menu.show_on_term(&term), Some(1));
+/// assert!(term.output().contains("Drinks"));
 /// ```
-pub struct Menu {
-    items: Vec<MenuOption>,
-    title: Option<String>,
-    message: Option<String>,
-    exit_on_action: bool,
-    bg_color: u8,
-    fg_color: u8,
-    title_color: u8,
-    selected_color: u8,
-    msg_color: u8,
-    selected_item: usize,
-    selected_page: usize,
-    items_per_page: usize,
-    num_pages: usize,
-    page_start: usize,
-    page_end: usize,
-    max_width: usize,
-}
+pub mod testing {
+    use std::collections::VecDeque;
+    use std::io;
+    use std::sync::{Arc, Condvar, Mutex};
 
-impl Menu {
-    pub fn new(items: Vec<MenuOption>, props: MenuProps) -> Self {
-        let mut items = items;
-        if items.len() == 0 { items.push(MenuOption::default()) }
-
-        let items_per_page: usize = (Term::stdout().size().0 - 6) as usize;
-        let items_per_page = clamp(items_per_page, 1, items.len());
-        let num_pages = ((items.len() - 1) / items_per_page) + 1;
-
-        let mut max_width = (&items).iter().fold(0, |max, item| {
-            let label_len = item.label.len();
-            if label_len > max { label_len } else { max }
-        });
-        if props.title.len() > max_width {
-            max_width = props.title.len()
-        }
-        if props.message.len() > max_width {
-            max_width = props.message.len()
-        }  
+    use crate::{Key, TerminalOutput};
 
-        let mut menu = Self {
-            items,
-            title: if props.title.len() > 0 {
-                Some(props.title.to_owned())
-            } else {
-                None
-            },
-            message: if props.message.len() > 0 {
-                Some(props.message.to_owned())
-            } else {
-                None
-            },
-            exit_on_action: props.exit_on_action,
-            bg_color: props.bg_color,
-            fg_color: props.fg_color,
-            title_color: props.title_color.unwrap_or(props.fg_color),
-            selected_color: props.selected_color.unwrap_or(props.fg_color),
-            msg_color: props.msg_color.unwrap_or(props.fg_color),
-            selected_item: 0,
-            selected_page: 0,
-            items_per_page,
-            num_pages,
-            page_start: 0,
-            page_end: 0,
-            max_width,
-        };
-        menu.set_page(0);
-        menu
+    /// A terminal that plays back a recorded sequence of keys and captures everything written to
+    /// it. Show a menu on it with `Menu::show_on_term(&term)`.
+    ///
+    /// Like a real terminal, it can be used from other threads, so loading indicators are
+    /// animated on it and resizes are noticed while the menu waits for a key.
+    pub struct MockTerm {
+        keys: Arc<Keys>,
+        wait_for_keys: bool,
+        writes: Arc<Mutex<Vec<String>>>,
+        size: Arc<Mutex<(u16, u16)>>,
     }
 
-    pub fn show(&mut self) {
-        let stdout = Term::buffered_stdout();
-        stdout.hide_cursor().unwrap();
-
-        let term_height = Term::stdout().size().0 as usize;
-        stdout.write_str(&"\n".repeat(term_height - 1)).unwrap();
-
-        self.draw(&stdout);
-        self.run_navigation(&stdout);
+    /// The keys left to read, and a signal for readers waiting on more.
+    struct Keys {
+        queue: Mutex<VecDeque<Key>>,
+        sent: Condvar,
     }
 
-    fn run_navigation(&mut self, stdout: &Term) {
-        loop {
-            let key = stdout.read_key().unwrap();
-
-            match key {
-                Key::ArrowUp | Key::Char('k') => {
-                    if self.selected_item != self.page_start {
-                        self.selected_item -= 1;
-                    } else if self.selected_page != 0 {
-                        self.set_page(self.selected_page - 1);
-                        self.selected_item = self.page_end;
-                    }
-                }
-                Key::ArrowDown | Key::Char('j') => {
-                    if self.selected_item < self.page_end {
-                        self.selected_item += 1
-                    } else if self.selected_page < self.num_pages - 1 {
-                        self.set_page(self.selected_page + 1);
-                    }
-                }
-                Key::ArrowLeft | Key::Char('h') | Key::Char('b') => {
-                    if self.selected_page != 0 {
-                        self.set_page(self.selected_page - 1);
-                    }
-                }
-                Key::ArrowRight | Key::Char('l') | Key::Char('w') => {
-                    if self.selected_page < self.num_pages - 1 {
-                        self.set_page(self.selected_page + 1);
-                    }
-                }
-                Key::Escape | Key::Char('q') | Key::Backspace => {
-                    self.exit(stdout);
-                    break;
-                }
-                Key::Enter => {
-                    if self.exit_on_action {
-                        self.exit(stdout);
-                        (self.items[self.selected_item].action)();
-                        break;
-                    } else {
-                        (self.items[self.selected_item].action)();
-                    }    
-                }
-                _ => {}
+    impl MockTerm {
+        /// Creates a 24 by 80 terminal that reads `keys` in order. Reading a key once they have
+        /// all been read fails, so a menu that's still open at the end of the sequence returns
+        /// an error from `try_show_on_term`.
+        pub fn new(keys: Vec<Key>) -> Self {
+            MockTerm {
+                keys: Arc::new(Keys { queue: Mutex::new(keys.into()), sent: Condvar::new() }),
+                wait_for_keys: false,
+                writes: Arc::new(Mutex::new(vec![])),
+                size: Arc::new(Mutex::new((24, 80))),
             }
+        }
 
-            self.draw(stdout);
+        /// Sets the size of the terminal as (rows, columns).
+        pub fn with_size(self, rows: u16, columns: u16) -> Self {
+            self.resize(rows, columns);
+            self
         }
-    }
 
-    fn set_page(&mut self, page: usize) {
-        self.selected_page = page;
-        self.page_start = self.selected_page * self.items_per_page;
-        self.selected_item = self.page_start;
-        if self.items.len() > self.page_start + self.items_per_page {
-            self.page_end = self.page_start + self.items_per_page - 1
-        } else {
-            self.page_end = self.items.len() - 1
+        /// Makes reading a key wait until one is sent with `send_key` once the keys have all been
+        /// read, instead of failing.
+        pub fn waiting_for_keys(mut self) -> Self {
+            self.wait_for_keys = true;
+            self
         }
-    }
 
-    fn draw(&self, stdout: &Term) {
-        clear_screen(stdout);
+        /// Adds a key to the end of the keys left to read, such as from another thread while a
+        /// menu is shown.
+        pub fn send_key(&self, key: Key) {
+            self.keys.queue.lock().unwrap().push_back(key);
+            self.keys.sent.notify_all();
+        }
 
-        let menu_width = self.max_width;
-        let mut extra_lines = 2;
-        if let Some(_) = self.title {
-           extra_lines += 2; 
+        /// Changes the size of the terminal to (rows, columns), such as from another thread while
+        /// a menu is shown.
+        pub fn resize(&self, rows: u16, columns: u16) {
+            *self.size.lock().unwrap() = (rows, columns);
         }
-        if let Some(_) = self.message {
-            extra_lines += 1;
+
+        /// Returns everything written to the terminal so far.
+        pub fn output(&self) -> String {
+            self.writes.lock().unwrap().concat()
         }
 
-        let indent: usize = (stdout.size().1 / 2) as usize - ((menu_width + 4) / 2);
-        let indent_str = pad_left("".to_string(), indent);
+        /// Returns each write to the terminal so far, in order.
+        pub fn writes(&self) -> Vec<String> {
+            self.writes.lock().unwrap().clone()
+        }
 
-        let vertical_pad: usize = (stdout.size().0 / 2) as usize  - ((self.items_per_page + extra_lines) / 2);
-        stdout.write_str(&format!("{:\n<width$}", "", width=vertical_pad)).unwrap();
+        /// Returns the output split into the frames drawn, each starting where the screen was
+        /// cleared.
+        ///
+        /// ```
+        /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+        /// # use console_menu::testing::MockTerm;
+        /// let term = MockTerm::new(vec![Key::ArrowRight, Key::Escape]);
+        /// let options = (1..=6).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+        /// let mut menu = Menu::new(options, MenuProps { page_size: Some(3), ..MenuProps::default() });
+        /// menu.show_on_term(&term);
+        ///
+        /// let frames = term.frames();
+        /// assert!(frames[0].contains("Page 1 of 2"));
+        /// assert!(frames[1].contains("Page 2 of 2"));
+        /// ```
+        pub fn frames(&self) -> Vec<String> {
+            self.output()
+                .split("\x1b[H\x1b[J")
+                .skip(1)
+                .filter(|frame| !frame.trim_start_matches("\x1b[H").is_empty())
+                .map(str::to_owned)
+                .collect()
+        }
 
-        stdout.write_str(&format!("\x1b[38;5;{}m", self.fg_color)).unwrap(); // set foreground color
-        stdout.write_line(&format!("{}{}", indent_str, self.apply_bg("", menu_width))).unwrap();
+        /// Returns the number of keys that haven't been read yet.
+        ///
+        /// ```
+        /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+        /// # use console_menu::testing::MockTerm;
+        /// let term = MockTerm::new(vec![Key::Escape, Key::Enter]);
+        /// let mut menu = Menu::new(vec![MenuOption::new("ok", || {})], MenuProps::default());
+        /// assert_eq!(menu.show_on_term(&term), None);
+        /// assert_eq!(term.remaining_keys(), 1);
+        ///
+        /// assert_eq!(menu.show_on_term(&term), Some(0));
+        /// assert!(menu.try_show_on_term(&term).is_err());
+        /// ```
+        pub fn remaining_keys(&self) -> usize {
+            self.keys.queue.lock().unwrap().len()
+        }
+    }
 
-        let mut ansi_width = 34 + num_digs(self.fg_color) + num_digs(self.title_color);
-        if let Some(title) = &self.title {
-            let title_str = format!("\x1b[4m{}\x1b[24m", self.apply_bold(title)); // apply bold + underline
-            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&self.switch_fg(&title_str, self.title_color), menu_width + ansi_width))).unwrap();
-            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg("", menu_width))).unwrap();
-        } 
+    impl TerminalOutput for MockTerm {
+        fn write_str(&self, s: &str) -> io::Result<()> {
+            self.writes.lock().unwrap().push(s.to_owned());
+            Ok(())
+        }
 
-        for (i, option) in self.items[self.page_start..=self.page_end].iter().enumerate() {
-            let item_str = if self.page_start + i == self.selected_item {
-                ansi_width = 25 + num_digs(self.fg_color) + num_digs(self.selected_color);
-                format!("{}", self.switch_fg(&self.apply_bold(&option.label), self.selected_color))
-            } else {
-                ansi_width = 0;
-                format!("{}", option.label)
-            };
-            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&item_str, menu_width + ansi_width))).unwrap();
+        fn size(&self) -> (u16, u16) {
+            *self.size.lock().unwrap()
         }
 
-        if self.num_pages > 1 {
-            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&format!("Page {} of {}", self.selected_page + 1, self.num_pages), menu_width))).unwrap();
+        fn read_key(&self) -> io::Result<Key> {
+            let mut queue = self.keys.queue.lock().unwrap();
+            while queue.is_empty() && self.wait_for_keys {
+                queue = self.keys.sent.wait(queue).unwrap();
+            }
+            queue.pop_front()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "no keys left to read"))
         }
-        if let Some(message) = &self.message {
-            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg("", menu_width))).unwrap();
-            stdout.write_line(&format!("{}{}", indent_str, self.switch_fg(&self.apply_bg(message, menu_width), self.msg_color))).unwrap();
+
+        fn clone_for_thread(&self) -> Option<Box<dyn TerminalOutput + Send>> {
+            Some(Box::new(MockTerm {
+                keys: Arc::clone(&self.keys),
+                wait_for_keys: self.wait_for_keys,
+                writes: Arc::clone(&self.writes),
+                size: Arc::clone(&self.size),
+            }))
         }
+    }
+}
 
-        stdout.write_line(&format!("{}{}", indent_str, self.apply_bg("", menu_width))).unwrap();
-        stdout.write_str("\x1b[39m").unwrap(); // reset foreground color
+/// A color used when drawing a menu.
+///
+/// The constants in the `color` module are plain 8-bit values so they can be compared and
+/// computed with directly; convert them with `.into()` (or `ColorMode::Color256`) where a
+/// `ColorMode` is expected. Builder and setter methods accept either form.
+///
+/// ```
+/// # use console_menu::{color, ColorMode, MenuProps};
+/// let props = MenuProps {
+///     bg_color: color::BLUE.into(),
+///     fg_color: ColorMode::TrueColor(255, 250, 240),
+///     ..MenuProps::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorMode {
+    /// An [8-bit](https://en.wikipedia.org/wiki/ANSI_escape_code#8-bit) color, supported by
+    /// almost every terminal.
+    Color256(u8),
+    /// A [24-bit](https://en.wikipedia.org/wiki/ANSI_escape_code#24-bit) red, green and blue
+    /// color. Not every terminal supports these.
+    TrueColor(u8, u8, u8),
+}
 
-        stdout.flush().unwrap();
+impl ColorMode {
+    /// Converts an 8-bit color to the equivalent 24-bit color. 24-bit colors are unchanged.
+    ///
+    /// ```
+    /// # use console_menu::{color, ColorMode};
+    /// let color = ColorMode::from(color::ORANGE).to_true_color();
+    /// assert_eq!(color, ColorMode::TrueColor(255, 135, 0));
+    /// ```
+    pub fn to_true_color(self) -> ColorMode {
+        match self {
+            ColorMode::Color256(color) => {
+                let (r, g, b) = color::to_rgb(color);
+                ColorMode::TrueColor(r, g, b)
+            }
+            true_color => true_color,
+        }
     }
 
-
-    fn apply_bold(&self, s: &str) -> String { // 9 ansi chars
-        format!("\x1b[1m{}\x1b[22m", s)
+    /// Converts this color to the closest one the terminal can display. Colors are unchanged for
+    /// `Monochrome` terminals, which should be drawn without colors instead.
+    ///
+    /// ```
+    /// # use console_menu::{terminal::ColorSupport, ColorMode};
+    /// let color = ColorMode::TrueColor(250, 5, 5);
+    /// assert_eq!(color.downgrade(ColorSupport::Color256), ColorMode::Color256(196));
+    /// assert_eq!(color.downgrade(ColorSupport::Color16), ColorMode::Color256(9));
+    /// ```
+    pub fn downgrade(self, support: ColorSupport) -> ColorMode {
+        match (self, support) {
+            (ColorMode::TrueColor(r, g, b), ColorSupport::Color256) => {
+                ColorMode::Color256(color::nearest((r, g, b), 16..=255))
+            }
+            (ColorMode::TrueColor(r, g, b), ColorSupport::Color16) => {
+                ColorMode::Color256(color::nearest((r, g, b), 0..=15))
+            }
+            (ColorMode::Color256(c), ColorSupport::Color16) if c > 15 => {
+                ColorMode::Color256(color::nearest(color::to_rgb(c), 0..=15))
+            }
+            (color, _) => color,
+        }
     }
 
-    fn switch_fg(&self, s: &str, color: u8) -> String { // 16 + (fg digs + switch digs) ansi chars
-        format!("\x1b[38;5;{}m{}\x1b[38;5;{}m", color, s, self.fg_color)
+    /// The escape sequence setting this as the foreground color.
+    ///
+    /// ```
+    /// # use console_menu::ColorMode;
+    /// assert_eq!(ColorMode::Color256(160).fg_escape(), "\x1b[38;5;160m");
+    /// assert_eq!(ColorMode::TrueColor(255, 0, 0).fg_escape(), "\x1b[38;2;255;0;0m");
+    /// ```
+    pub fn fg_escape(self) -> String {
+        match self {
+            ColorMode::Color256(color) => format!("\x1b[38;5;{}m", color),
+            ColorMode::TrueColor(r, g, b) => format!("\x1b[38;2;{};{};{}m", r, g, b),
+        }
     }
 
-    fn apply_bg(&self, s: &str, width: usize) -> String {
-        format!("\x1b[48;5;{}m{}\x1b[49m", self.bg_color, pad_right(format!("  {}", s), width + 4)) 
+    /// The escape sequence setting this as the background color.
+    ///
+    /// ```
+    /// # use console_menu::ColorMode;
+    /// assert_eq!(ColorMode::Color256(8).bg_escape(), "\x1b[48;5;8m");
+    /// assert_eq!(ColorMode::TrueColor(0, 0, 128).bg_escape(), "\x1b[48;2;0;0;128m");
+    /// ```
+    pub fn bg_escape(self) -> String {
+        match self {
+            ColorMode::Color256(color) => format!("\x1b[48;5;{}m", color),
+            ColorMode::TrueColor(r, g, b) => format!("\x1b[48;2;{};{};{}m", r, g, b),
+        }
     }
+}
 
+impl From<u8> for ColorMode {
+    fn from(color: u8) -> Self {
+        ColorMode::Color256(color)
+    }
+}
 
-    fn exit(&self, stdout: &Term) {
-        clear_screen(stdout);
-        stdout.show_cursor().unwrap();
-        stdout.flush().unwrap();
+impl std::fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorMode::Color256(color) => write!(f, "{}", color),
+            ColorMode::TrueColor(r, g, b) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+        }
     }
 }
 
+/// How the options of a menu are arranged.
+///
+/// ```
+/// # use console_menu::{Key, Menu, MenuLayout, MenuOption, MenuProps};
+/// # use console_menu::testing::MockTerm;
+/// let term = MockTerm::new(vec![Key::ArrowRight, Key::Enter]);
+/// let mut menu = Menu::new(vec![
+///     MenuOption::with_value("Yes", 'y'),
+///     MenuOption::with_value("No", 'n'),
+///     MenuOption::with_value("Cancel", 'c'),
+/// ], MenuProps { layout: MenuLayout::Horizontal, no_color: true, ..MenuProps::default() });
+///
+/// assert_eq!(menu.show_on_term(&term), Some(1));
+/// let output = term.output();
+/// assert!(output.lines().any(|line| line.contains("Yes") && line.contains("No") && line.contains("Cancel")));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MenuLayout {
+    /// Options are shown one per row, or in `columns`. Up and down move between them.
+    #[default]
+    Vertical,
+    /// Options are shown side by side in a single row, split by `separator_str`. Left and right
+    /// move between them, and pages hold as many options as fit in the terminal's width.
+    Horizontal,
+}
+
+/// Where a menu is placed across the width of the terminal.
+///
+/// ```
+/// # use console_menu::{BorderStyle, Key, Menu, MenuAlign, MenuOption, MenuProps};
+/// # use console_menu::testing::MockTerm;
+/// let term = MockTerm::new(vec![Key::Escape]);
+/// let mut menu = Menu::new(vec![MenuOption::new("ok", || {})], MenuProps {
+///     border_style: BorderStyle::Single,
+///     menu_align: MenuAlign::Left,
+///     no_color: true,
+///     ..MenuProps::default()
+/// });
+/// menu.show_on_term(&term);
+///
+/// let output = term.output();
+/// assert!(output.lines().any(|line| line.starts_with('┌')));
+/// assert!(output.lines().any(|line| line.starts_with('└')));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MenuAlign {
+    /// Centered in the terminal.
+    #[default]
+    Center,
+    /// Against the left edge of the terminal.
+    Left,
+    /// Against the right edge of the terminal.
+    Right,
+    /// The given number of columns from the left edge of the terminal.
+    Fixed(usize),
+}
 
-fn clear_screen(stdout: &Term) {
-    stdout.write_str("\x1b[H\x1b[J\x1b[H").unwrap();
+/// Where a menu is placed across the height of the terminal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MenuVAlign {
+    /// Against the top of the terminal.
+    Top,
+    /// Centered in the terminal.
+    #[default]
+    Center,
+    /// Against the bottom of the terminal.
+    Bottom,
 }
 
-fn pad_left(s: String, width: usize) -> String {
-    format!("{: >width$}", s, width=width)
+/// Whether the selection wraps around at the edges of a menu.
+///
+/// ```
+/// # use console_menu::{Key, Menu, MenuOption, MenuProps, WrapMode};
+/// let menu_with = |wrap_mode| {
+///     let options = (1..=6).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+///     Menu::new(options, MenuProps { page_size: Some(3), wrap_mode, ..MenuProps::default() })
+/// };
+/// let selected_after = |wrap_mode, keys: &[Key]| {
+///     let mut menu = menu_with(wrap_mode);
+///     for key in keys {
+///         menu.handle_key(key.clone());
+///     }
+///     let snapshot = menu.snapshot();
+///     (snapshot.selected_page, snapshot.selected_option)
+/// };
+///
+/// // moving down from the last option of the first page
+/// let down = [Key::ArrowDown, Key::ArrowDown, Key::ArrowDown];
+/// assert_eq!(selected_after(WrapMode::NoWrap, &down), (1, 3));
+/// assert_eq!(selected_after(WrapMode::WrapOptions, &down), (0, 0));
+/// assert_eq!(selected_after(WrapMode::WrapPages, &down), (1, 3));
+/// assert_eq!(selected_after(WrapMode::WrapBoth, &down), (0, 0));
+///
+/// // moving up from the first option
+/// assert_eq!(selected_after(WrapMode::NoWrap, &[Key::ArrowUp]), (0, 0));
+/// assert_eq!(selected_after(WrapMode::WrapOptions, &[Key::ArrowUp]), (0, 2));
+/// assert_eq!(selected_after(WrapMode::WrapPages, &[Key::ArrowUp]), (1, 5));
+/// assert_eq!(selected_after(WrapMode::WrapBoth, &[Key::ArrowUp]), (0, 2));
+///
+/// // moving right from the last page
+/// let right = [Key::ArrowRight, Key::ArrowRight];
+/// assert_eq!(selected_after(WrapMode::NoWrap, &right), (1, 3));
+/// assert_eq!(selected_after(WrapMode::WrapOptions, &right), (1, 3));
+/// assert_eq!(selected_after(WrapMode::WrapPages, &right), (0, 0));
+/// assert_eq!(selected_after(WrapMode::WrapBoth, &right), (0, 0));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WrapMode {
+    /// The selection stops at the first and last option, and at the first and last page.
+    #[default]
+    NoWrap,
+    /// Up and down wrap around within the current page, never moving to another page.
+    WrapOptions,
+    /// Up and down move across pages, wrapping from the last option to the first. Left and right
+    /// wrap from the last page to the first.
+    WrapPages,
+    /// Up and down wrap around within the current page, and left and right wrap around the pages.
+    WrapBoth,
 }
 
-fn pad_right(s: String, width: usize) -> String {
-    format!("{: <width$}", s, width=width)
+impl WrapMode {
+    fn wraps_options(self) -> bool {
+        matches!(self, WrapMode::WrapOptions | WrapMode::WrapBoth)
+    }
+
+    fn wraps_pages(self) -> bool {
+        matches!(self, WrapMode::WrapPages | WrapMode::WrapBoth)
+    }
 }
 
-fn clamp(num: usize, min: usize, max: usize) -> usize {
-    let out = if num < min { min } else { num };
-    if out > max { max } else { out }
+/// The screen buffer a menu is drawn on.
+///
+/// ```
+/// # use console_menu::{Key, Menu, MenuOption, MenuProps, ScreenMode};
+/// # use console_menu::testing::MockTerm;
+/// let term = MockTerm::new(vec![Key::Escape]);
+/// let mut menu = Menu::new(vec![MenuOption::new("ok", || {})], MenuProps {
+///     screen_mode: ScreenMode::Alternate,
+///     ..MenuProps::default()
+/// });
+/// menu.show_on_term(&term);
+///
+/// let output = term.output();
+/// assert!(output.starts_with("\x1b[?1049h"));
+/// assert!(output.ends_with("\x1b[?1049l"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScreenMode {
+    /// The terminal's main screen, scrolled up before the menu is drawn to keep its content in the
+    /// scrollback unless `clear_on_show` is false.
+    #[default]
+    Main,
+    /// The terminal's alternate screen, like `less` and other full-screen programs use. The main
+    /// screen is left untouched and comes back once the menu exits.
+    Alternate,
 }
 
-fn num_digs(num: u8) -> usize {
-    (num.checked_ilog10().unwrap_or(0) + 1) as usize
+impl ScreenMode {
+    fn enter_sequence(self) -> &'static str {
+        match self {
+            ScreenMode::Main => "",
+            ScreenMode::Alternate => "\x1b[?1049h",
+        }
+    }
+
+    fn leave_sequence(self) -> &'static str {
+        match self {
+            ScreenMode::Main => "",
+            ScreenMode::Alternate => "\x1b[?1049l",
+        }
+    }
+}
+
+/// Space left before the menu, above it for `vertical_padding` or to its left for
+/// `horizontal_padding`.
+///
+/// ```
+/// # use console_menu::{BorderStyle, Key, Menu, MenuOption, MenuPadding, MenuProps};
+/// # use console_menu::testing::MockTerm;
+/// let term = MockTerm::new(vec![Key::Escape]);
+/// let mut menu = Menu::new(vec![MenuOption::new("ok", || {})], MenuProps {
+///     vertical_padding: MenuPadding::Fixed(2),
+///     horizontal_padding: MenuPadding::Fixed(0),
+///     border_style: BorderStyle::Single,
+///     no_color: true,
+///     ..MenuProps::default()
+/// });
+/// menu.show_on_term(&term);
+///
+/// let output = term.output();
+/// let frame = output.split("\x1b[H\x1b[J\x1b[H").nth(1).unwrap();
+/// assert!(frame.starts_with("\n\n┌"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MenuPadding {
+    /// Positioned by `menu_align` or `menu_valign`, centered by default.
+    #[default]
+    Auto,
+    /// The given number of rows or columns.
+    Fixed(usize),
+    /// The given percentage of the terminal's height or width.
+    Percent(u8),
+}
+
+impl MenuPadding {
+    /// The padding in rows or columns for a terminal `size` rows or columns long, or None if the
+    /// menu is positioned by its alignment.
+    fn resolve(&self, size: usize) -> Option<usize> {
+        match self {
+            MenuPadding::Auto => None,
+            MenuPadding::Fixed(padding) => Some(*padding),
+            MenuPadding::Percent(percent) => Some(size * (*percent).min(100) as usize / 100),
+        }
+    }
+}
+
+/// Where the title is placed within the width of the menu. The message is aligned with the same
+/// values, under the name `MessageAlign`.
+///
+/// ```
+/// # use console_menu::{Key, Menu, MenuOption, MenuProps, TitleAlign};
+/// # use console_menu::testing::MockTerm;
+/// let term = MockTerm::new(vec![Key::Escape]);
+/// let mut menu = Menu::new(vec![MenuOption::new("ok", || {})], MenuProps {
+///     title: "Ten chars!",
+///     message: "this message is 30 chars wide!",
+///     title_align: TitleAlign::Center,
+///     no_color: true,
+///     ..MenuProps::default()
+/// });
+/// menu.show_on_term(&term);
+///
+/// let output = term.output();
+/// let column = |text| output.lines().find_map(|line| line.find(text)).unwrap();
+/// assert_eq!(column("Ten chars!") - column("this message"), 10);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TitleAlign {
+    /// Against the left edge of the menu.
+    #[default]
+    Left,
+    /// Centered within the menu, leaning left when it can't be exactly centered.
+    Center,
+    /// Against the right edge of the menu.
+    Right,
+}
+
+impl TitleAlign {
+    /// Spaces to put before text that leaves `free` columns of the menu empty.
+    fn padding(&self, free: usize) -> usize {
+        match self {
+            TitleAlign::Left => 0,
+            TitleAlign::Center => free / 2,
+            TitleAlign::Right => free,
+        }
+    }
+}
+
+/// Where the message, or the selected option's tooltip, is placed within the width of the menu.
+/// It's aligned the same ways as the title, so `MessageAlign::Center` and `TitleAlign::Center`
+/// are the same value.
+pub type MessageAlign = TitleAlign;
+
+/// The lines drawn around the edge of a menu.
+///
+/// ```
+/// # use console_menu::{BorderStyle, Key, Menu, MenuOption, MenuProps};
+/// # use console_menu::testing::MockTerm;
+/// # let term = MockTerm::new(vec![Key::Escape]);
+/// let mut menu = Menu::new(vec![MenuOption::new("option", || {})], MenuProps {
+///     border_style: BorderStyle::Rounded,
+///     ..MenuProps::default()
+/// });
+/// menu.show_on_term(&term);
+///
+/// let output = term.output();
+/// for corner in ['╭', '╮', '╰', '╯', '│', '─'] {
+///     assert!(output.contains(corner));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// No lines, only the menu background.
+    #[default]
+    None,
+    /// ┌─┐ thin lines with square corners.
+    Single,
+    /// ╔═╗ double lines.
+    Double,
+    /// ╭─╮ thin lines with rounded corners.
+    Rounded,
+    /// ┏━┓ thick lines.
+    Thick,
+}
+
+struct BorderChars {
+    horizontal: char,
+    vertical: char,
+    top_left: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_right: char,
+}
+
+impl BorderStyle {
+    fn chars(self) -> Option<BorderChars> {
+        let [horizontal, vertical, top_left, top_right, bottom_left, bottom_right] = match self {
+            BorderStyle::None => return None,
+            BorderStyle::Single => ['─', '│', '┌', '┐', '└', '┘'],
+            BorderStyle::Double => ['═', '║', '╔', '╗', '╚', '╝'],
+            BorderStyle::Rounded => ['─', '│', '╭', '╮', '╰', '╯'],
+            BorderStyle::Thick => ['━', '┃', '┏', '┓', '┗', '┛'],
+        };
+        Some(BorderChars { horizontal, vertical, top_left, top_right, bottom_left, bottom_right })
+    }
+}
+
+/// Stores configuration data passed to a `Menu` on creation.
+///
+/// Menus use [8-bit](https://en.wikipedia.org/wiki/ANSI_escape_code#8-bit) colors by default to
+/// ensure widespread terminal support. It should be noted that values from 0-15 will make colors
+/// vary based on individual terminal settings. 24-bit colors can be used on terminals that support
+/// them, see `ColorMode`.
+///
+/// Configure a subset of properties using the defaults and struct update syntax:
+/// ```
+/// # use console_menu::MenuProps;
+/// let props = MenuProps {
+///     title: "My Menu",
+///     ..MenuProps::default()
+/// };
+/// ```
+#[derive(Clone)]
+pub struct MenuProps<'a> {
+    /// Displays above the list of menu options. Pass an empty string for no title.
+    pub title: &'a str,
+    /// Displayed before the title in the title color, e.g. an icon. Has no effect without a title.
+    pub title_prefix: &'a str,
+    /// Displayed after the title in the title color, e.g. a version number. Has no effect without
+    /// a title.
+    pub title_suffix: &'a str,
+    /// Display below the list of menu options. Pass an empty string for no message.
+    pub message: &'a str,
+    /// If true, menu will exit immediately upon an option being selected.
+    pub exit_on_action: bool,
+    /// If true, menu stays open after an option is selected so several actions can be run in
+    /// sequence, regardless of `exit_on_action`. The menu exits on esc.
+    pub multi_run_mode: bool,
+    /// The background color for the menu.
+    pub bg_color: ColorMode,
+    /// The foreground (text) color for the menu.
+    pub fg_color: ColorMode,
+    /// Optional color for the title. If None, the foreground color will be used.
+    pub title_color: Option<ColorMode>,
+    /// Optional color for the selected menu option. If None, the foreground color will be used.
+    pub selected_color: Option<ColorMode>,
+    /// Optional color for the footer message. If None, the foreground color will be used.
+    pub msg_color: Option<ColorMode>,
+    /// Optional color for disabled menu options. If None, the foreground color will be used with
+    /// dimmed text.
+    pub disabled_color: Option<ColorMode>,
+    /// Optional callback run whenever the menu switches pages, receiving the new (0-based) page
+    /// index. The page change cannot be aborted from the callback.
+    pub page_change_callback: Option<Rc<dyn Fn(usize)>>,
+    /// Time in milliseconds a key must be held before repeated presses are processed. 0 disables
+    /// the delay.
+    pub key_repeat_delay_ms: u64,
+    /// Minimum time in milliseconds between processing repeated presses of a held key. 0 disables
+    /// throttling.
+    pub key_repeat_rate_ms: u64,
+    /// If true, space toggles options on and off and enter confirms the checked options, which are
+    /// returned from `Menu::show_multi`. Option actions are not run in this mode.
+    ///
+    /// On top of the regular controls, a checks every option on the current page and c clears
+    /// all checked options.
+    pub multi_select: bool,
+    /// Displayed between brackets before checked options in multi-select mode, and before toggle
+    /// options that are on.
+    pub check_char: char,
+    /// Displayed between brackets before unchecked options in multi-select mode, and before toggle
+    /// options that are off.
+    pub uncheck_char: char,
+    /// Character repeated to draw separator options.
+    pub separator_char: char,
+    /// If true, moving up from the first option selects the last option and moving down from the
+    /// last option selects the first, when `wrap_mode` is `WrapMode::NoWrap`.
+    #[deprecated(since = "0.4.0", note = "use `wrap_mode: WrapMode::WrapPages` instead")]
+    pub wrap_selection: bool,
+    /// Optional number of options per page. If None, as many options as fit in the terminal
+    /// window are shown on each page.
+    pub page_size: Option<usize>,
+    /// The keys used to control the menu.
+    pub key_bindings: KeyBindings,
+    /// If true, the menu is displayed as plain text without colors or text styles, with a `>`
+    /// marking the selected option. Plain output is also used whenever the `NO_COLOR`
+    /// environment variable is set (see <https://no-color.org>).
+    pub no_color: bool,
+    /// Optional maximum width of option labels in terminal columns. Longer labels are cut short
+    /// and end in `…`. If None, the menu is made wide enough to fit every label.
+    pub max_label_width: Option<usize>,
+    /// Optional hint of the colors supported by the terminal, such as `terminal::color_support()`.
+    /// Colors are converted to the closest supported ones when drawn, and `Monochrome` draws the
+    /// menu without colors like `no_color`. If None, colors are used as given.
+    pub color_support: Option<ColorSupport>,
+    /// Lines drawn around the edge of the menu. With `BorderStyle::None`, the menu is outlined by
+    /// its background color alone.
+    pub border_style: BorderStyle,
+    /// Optional color for the border. If None, the foreground color will be used.
+    pub border_color: Option<ColorMode>,
+    /// If true, the empty rows around the options, title and message are left out so the menu
+    /// takes up less vertical space. Borders are still drawn.
+    pub compact: bool,
+    /// Optional number of columns to lay options out in. Options fill each column from top to
+    /// bottom before moving to the next, and left and right move between columns before changing
+    /// pages. If None, options are shown in a single column.
+    pub columns: Option<usize>,
+    /// Optional color for the part of each option matching the search query. If None, matches are
+    /// underlined.
+    pub match_color: Option<ColorMode>,
+    /// If true, the first nine options on each page are numbered. Pressing a number selects and
+    /// confirms the option with that number on the current page, whether or not numbers are shown.
+    pub show_shortcuts: bool,
+    /// If true, showing the menu again starts with the option that was selected when it last
+    /// closed. Otherwise every `show` starts on the first option.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let picks_twice = |remember_position| {
+    ///     let options = (0..5).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+    ///     let mut menu = Menu::new(options, MenuProps { remember_position, ..MenuProps::default() });
+    ///     let term = MockTerm::new(vec![Key::ArrowDown, Key::ArrowDown, Key::ArrowDown, Key::Enter, Key::Enter]);
+    ///     (menu.show_on_term(&term), menu.show_on_term(&term))
+    /// };
+    ///
+    /// assert_eq!(picks_twice(false), (Some(3), Some(0)));
+    /// assert_eq!(picks_twice(true), (Some(3), Some(3)));
+    /// ```
+    pub remember_position: bool,
+    /// Optional text displayed before the selected option, such as `"→ "`. Replaces the `>` shown
+    /// in plain mode.
+    pub selection_prefix: Option<&'a str>,
+    /// Optional text displayed before options that aren't selected when `selection_prefix` is set.
+    /// If None, spaces as wide as `selection_prefix` are used.
+    pub normal_prefix: Option<&'a str>,
+    /// If false, the selected option isn't drawn in bold when `selection_prefix` is set. The
+    /// selected option is always bold without a selection prefix.
+    pub bold_selection: bool,
+    /// Optional function drawing the header row in place of the title and the empty row below it,
+    /// called on every redraw. The returned text should be a single row no wider than the menu.
+    pub header_renderer: Option<Rc<dyn Fn() -> String>>,
+    /// Optional function drawing the footer row in place of the message and the empty row above
+    /// it, called on every redraw. The returned text should be a single row no wider than the menu.
+    pub footer_renderer: Option<Rc<dyn Fn() -> String>>,
+    /// If true, paginated menus show a scrollbar along the right edge of the options instead of
+    /// the `pagination_format` row.
+    pub show_scrollbar: bool,
+    /// How the options are arranged, vertically by default.
+    pub layout: MenuLayout,
+    /// Text drawn between options in the horizontal layout.
+    pub separator_str: &'a str,
+    /// Where the title is placed within the width of the menu.
+    pub title_align: TitleAlign,
+    /// Where the message is placed within the width of the menu.
+    pub msg_align: MessageAlign,
+    /// Where the menu is placed across the width of the terminal, centered by default.
+    pub menu_align: MenuAlign,
+    /// Where the menu is placed across the height of the terminal, centered by default.
+    pub menu_valign: MenuVAlign,
+    /// Optional titles of the menus leading to this one, shown dimmed above the title as
+    /// `Main > Settings > Colors`. Nested menus pass the trail on themselves, see
+    /// `BreadcrumbBuilder`.
+    pub breadcrumb: Option<Vec<String>>,
+    /// If true, the keys bound to the main commands are listed on the bottom row of the terminal,
+    /// below the menu, like `↑/↓ Navigate   Enter Confirm   / Search   Esc Exit`.
+    ///
+    /// ```
+    /// # use console_menu::{BorderStyle, Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let term = MockTerm::new(vec![Key::Escape]);
+    /// let mut menu = Menu::new(vec![MenuOption::new("ok", || {})], MenuProps {
+    ///     show_key_hints: true,
+    ///     border_style: BorderStyle::Single,
+    ///     ..MenuProps::default()
+    /// });
+    /// menu.show_on_term(&term);
+    ///
+    /// let output = term.output();
+    /// let hints = &output[output.rfind('┘').unwrap()..];
+    /// assert!(hints.contains("Enter") && hints.contains("Esc"));
+    /// ```
+    pub show_key_hints: bool,
+    /// Rows left empty above the menu. `Auto` positions it by `menu_valign` instead.
+    pub vertical_padding: MenuPadding,
+    /// Columns left empty to the left of the menu. `Auto` positions it by `menu_align` instead.
+    pub horizontal_padding: MenuPadding,
+    /// If true, a spinner and "Running…" are shown in place of the message while the action of a
+    /// selected option runs, for menus that stay open after an action. The action runs on the
+    /// calling thread while a background thread redraws the spinner every 100ms, on terminals that
+    /// provide `TerminalOutput::clone_for_thread`. Other terminals show the first frame only.
+    ///
+    /// ```
+    /// # use std::thread;
+    /// # use std::time::Duration;
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let term = MockTerm::new(vec![Key::Enter, Key::Escape]);
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("sync", || thread::sleep(Duration::from_millis(650))),
+    /// ], MenuProps {
+    ///     message: "ready",
+    ///     exit_on_action: false,
+    ///     show_loading: true,
+    ///     clear_on_exit: false,
+    ///     ..MenuProps::default()
+    /// });
+    /// menu.show_on_term(&term);
+    ///
+    /// // the spinner turns while the action runs
+    /// let frames = term.frames();
+    /// let spinner: Vec<char> = frames.iter()
+    ///     .filter_map(|frame| frame.find(" Running…").map(|i| frame[..i].chars().last().unwrap()))
+    ///     .collect();
+    /// let order = ['|', '/', '-', '\\'];
+    /// assert!(spinner.len() >= 2 && spinner[0] == '|');
+    /// for pair in spinner.windows(2) {
+    ///     let next = order.iter().position(|&c| c == pair[0]).unwrap() + 1;
+    ///     assert_eq!(pair[1], order[next % order.len()]);
+    /// }
+    /// // and the message is back once it's done
+    /// let last_frame = frames.last().unwrap();
+    /// assert!(last_frame.contains("ready") && !last_frame.contains("Running…"));
+    /// ```
+    pub show_loading: bool,
+    /// If true, the menu is displayed on stderr instead of stdout, keeping stdout clean for piped
+    /// output like `Menu::show_on_stderr`.
+    pub render_to_stderr: bool,
+    /// Whether moving past the first or last option, or the first or last page, wraps around to
+    /// the other end. See `WrapMode`.
+    pub wrap_mode: WrapMode,
+    /// If true, the terminal is scrolled by its height before the menu is first drawn, keeping what
+    /// was on screen in the scrollback. If false, the menu is drawn over the screen straight away,
+    /// e.g. to follow another menu without leaving a blank screen in the scrollback.
+    pub clear_on_show: bool,
+    /// If true, the screen is cleared when the menu exits. If false, the last frame of the menu is
+    /// left on screen and the cursor moves to the line below it, so output printed after the menu
+    /// follows it.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let term = MockTerm::new(vec![Key::ArrowDown, Key::Enter]);
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::with_value("keep", 1),
+    ///     MenuOption::with_value("discard", 2),
+    /// ], MenuProps { clear_on_exit: false, ..MenuProps::default() });
+    ///
+    /// assert_eq!(menu.show_on_term(&term), Some(1));
+    /// let output = term.output();
+    /// let last_frame = &output[output.rfind("\x1b[H\x1b[J").unwrap()..];
+    /// assert!(last_frame.contains("keep") && last_frame.contains("discard"));
+    /// // one clear for each of the two frames drawn, and none on exit
+    /// assert_eq!(output.matches("\x1b[H\x1b[J").count(), 2);
+    /// ```
+    pub clear_on_exit: bool,
+    /// Which screen buffer of the terminal the menu is drawn on. See `ScreenMode`.
+    pub screen_mode: ScreenMode,
+    /// If true, the menu is drawn on the alternate screen whatever `screen_mode` is set to.
+    #[deprecated(since = "0.4.0", note = "use `screen_mode: ScreenMode::Alternate` instead")]
+    pub use_alternate_screen: bool,
+    /// Optional text of the row below the options of paginated menus, where `{page}` is replaced by
+    /// the selected page number and `{total}` by the number of pages. If None, the row isn't shown.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let shown_with = |pagination_format| {
+    ///     let term = MockTerm::new(vec![Key::ArrowRight, Key::Escape]);
+    ///     let options = (1..=5).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+    ///     let mut menu = Menu::new(options, MenuProps { page_size: Some(2), pagination_format, ..MenuProps::default() });
+    ///     menu.show_on_term(&term);
+    ///     term.output()
+    /// };
+    ///
+    /// let output = shown_with(Some("{page}/{total}"));
+    /// assert!(output.contains("1/3") && output.contains("2/3"));
+    ///
+    /// assert!(shown_with(Some("Page {page} of {total}")).contains("Page 1 of 3"));
+    /// assert!(!shown_with(None).contains("Page 1 of 3"));
+    /// ```
+    pub pagination_format: Option<&'a str>,
+    /// If true, the title is drawn in its own box inside the menu, in the `border_style` and
+    /// `border_color` of the menu. Has no effect on menus without a border, or with a
+    /// `header_renderer`.
+    ///
+    /// ```
+    /// # use console_menu::{BorderStyle, Menu, MenuOption, MenuProps};
+    /// let mut menu = Menu::new(vec![MenuOption::new("dark mode", || {})], MenuProps {
+    ///     title: "Settings",
+    ///     title_border: true,
+    ///     border_style: BorderStyle::Single,
+    ///     no_color: true,
+    ///     ..MenuProps::default()
+    /// });
+    ///
+    /// let output = menu.render_to_string(30, 12);
+    /// let rows: Vec<&str> = output.lines().map(str::trim).filter(|row| !row.is_empty()).collect();
+    /// assert_eq!(&rows[..4], ["┌─────────────┐", "│ ┌─────────┐ │", "│ │Settings │ │", "│ └─────────┘ │"]);
+    /// ```
+    pub title_border: bool,
+}
+
+impl<'a> MenuProps<'a> {
+    /// Creates a `MenuPropsBuilder` starting from the default properties.
+    ///
+    /// ```
+    /// # use console_menu::{color, MenuProps};
+    /// let props = MenuProps::builder()
+    ///     .title("My Menu")
+    ///     .bg_color(color::DARK_GRAY)
+    ///     .selected_color(color::YELLOW)
+    ///     .exit_on_action(false)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> MenuPropsBuilder<'a> {
+        MenuPropsBuilder {
+            props: MenuProps::default(),
+        }
+    }
+
+    /// Creates the default properties with `multi_select` enabled.
+    ///
+    /// ```
+    /// # use console_menu::MenuProps;
+    /// let props = MenuProps {
+    ///     title: "Files to process",
+    ///     ..MenuProps::multi_select()
+    /// };
+    /// ```
+    pub fn multi_select() -> MenuProps<'a> {
+        MenuProps {
+            multi_select: true,
+            ..MenuProps::default()
+        }
+    }
+
+    /// Creates the default properties with all colors taken from `theme`.
+    ///
+    /// ```
+    /// # use console_menu::{theme, MenuProps};
+    /// let props = MenuProps::with_theme(&theme::FOREST);
+    /// assert_eq!(props.bg_color, theme::FOREST.bg_color);
+    /// assert_eq!(props.fg_color, theme::FOREST.fg_color);
+    /// assert_eq!(props.title_color, theme::FOREST.title_color);
+    /// assert_eq!(props.selected_color, theme::FOREST.selected_color);
+    /// assert_eq!(props.msg_color, theme::FOREST.msg_color);
+    /// ```
+    pub fn with_theme(theme: &theme::Theme) -> MenuProps<'a> {
+        MenuProps {
+            bg_color: theme.bg_color,
+            fg_color: theme.fg_color,
+            title_color: theme.title_color,
+            selected_color: theme.selected_color,
+            msg_color: theme.msg_color,
+            ..MenuProps::default()
+        }
+    }
+
+    /// Sets `page_change_callback`.
+    ///
+    /// ```
+    /// # use console_menu::MenuProps;
+    /// let props = MenuProps::default()
+    ///     .with_page_change_callback(|page| eprintln!("switched to page {}", page + 1));
+    /// ```
+    pub fn with_page_change_callback(mut self, f: impl Fn(usize) + 'static) -> Self {
+        self.page_change_callback = Some(Rc::new(f));
+        self
+    }
+
+    /// Sets `breadcrumb` to the titles of the menus leading to this one.
+    ///
+    /// ```
+    /// # use console_menu::MenuProps;
+    /// let props = MenuProps {
+    ///     title: "Colors",
+    ///     ..MenuProps::default()
+    /// }.with_breadcrumb(vec!["Main".to_owned(), "Settings".to_owned()]);
+    /// ```
+    pub fn with_breadcrumb(mut self, crumbs: Vec<String>) -> Self {
+        self.breadcrumb = Some(crumbs);
+        self
+    }
+}
+
+/// ```
+/// # use console_menu::{BorderStyle, ColorMode, KeyBindings, MenuAlign, MenuLayout, MenuPadding, MenuProps, MenuVAlign, MessageAlign, ScreenMode, TitleAlign, WrapMode};
+/// # #[allow(deprecated)]
+/// # fn default() -> MenuProps<'static> {
+/// MenuProps {
+///     title: "",
+///     title_prefix: "",
+///     title_suffix: "",
+///     message: "",
+///     exit_on_action: true,
+///     multi_run_mode: false,
+///     bg_color: ColorMode::Color256(8),
+///     fg_color: ColorMode::Color256(15),
+///     title_color: None,
+///     selected_color: None,
+///     msg_color: Some(ColorMode::Color256(7)),
+///     disabled_color: None,
+///     page_change_callback: None,
+///     key_repeat_delay_ms: 0,
+///     key_repeat_rate_ms: 0,
+///     multi_select: false,
+///     check_char: 'x',
+///     uncheck_char: ' ',
+///     separator_char: '─',
+///     wrap_selection: false,
+///     page_size: None,
+///     key_bindings: KeyBindings::default(),
+///     no_color: false,
+///     max_label_width: None,
+///     color_support: None,
+///     border_style: BorderStyle::None,
+///     border_color: None,
+///     compact: false,
+///     columns: None,
+///     match_color: None,
+///     show_shortcuts: false,
+///     remember_position: false,
+///     selection_prefix: None,
+///     normal_prefix: None,
+///     bold_selection: true,
+///     header_renderer: None,
+///     footer_renderer: None,
+///     show_scrollbar: false,
+///     layout: MenuLayout::Vertical,
+///     separator_str: " │ ",
+///     title_align: TitleAlign::Left,
+///     msg_align: MessageAlign::Left,
+///     menu_align: MenuAlign::Center,
+///     menu_valign: MenuVAlign::Center,
+///     breadcrumb: None,
+///     show_key_hints: false,
+///     vertical_padding: MenuPadding::Auto,
+///     horizontal_padding: MenuPadding::Auto,
+///     show_loading: false,
+///     render_to_stderr: false,
+///     wrap_mode: WrapMode::NoWrap,
+///     clear_on_show: true,
+///     clear_on_exit: true,
+///     screen_mode: ScreenMode::Main,
+///     use_alternate_screen: false,
+///     pagination_format: Some("Page {page} of {total}"),
+///     title_border: false,
+/// }
+/// # }
+/// ```
+impl Default for MenuProps<'_> {
+    #[allow(deprecated)]
+    fn default() -> MenuProps<'static> {
+        MenuProps {
+            title: "",
+            title_prefix: "",
+            title_suffix: "",
+            message: "",
+            exit_on_action: true,
+            multi_run_mode: false,
+            bg_color: ColorMode::Color256(8),
+            fg_color: ColorMode::Color256(15),
+            title_color: None,
+            selected_color: None,
+            msg_color: Some(ColorMode::Color256(7)),
+            disabled_color: None,
+            page_change_callback: None,
+            key_repeat_delay_ms: 0,
+            key_repeat_rate_ms: 0,
+            multi_select: false,
+            check_char: 'x',
+            uncheck_char: ' ',
+            separator_char: '─',
+            wrap_selection: false,
+            page_size: None,
+            key_bindings: KeyBindings::default(),
+            no_color: false,
+            max_label_width: None,
+            color_support: None,
+            border_style: BorderStyle::None,
+            border_color: None,
+            compact: false,
+            columns: None,
+            match_color: None,
+            show_shortcuts: false,
+            remember_position: false,
+            selection_prefix: None,
+            normal_prefix: None,
+            bold_selection: true,
+            header_renderer: None,
+            footer_renderer: None,
+            show_scrollbar: false,
+            layout: MenuLayout::Vertical,
+            separator_str: " │ ",
+            title_align: TitleAlign::Left,
+            msg_align: MessageAlign::Left,
+            menu_align: MenuAlign::Center,
+            menu_valign: MenuVAlign::Center,
+            breadcrumb: None,
+            show_key_hints: false,
+            vertical_padding: MenuPadding::Auto,
+            horizontal_padding: MenuPadding::Auto,
+            show_loading: false,
+            render_to_stderr: false,
+            wrap_mode: WrapMode::NoWrap,
+            clear_on_show: true,
+            clear_on_exit: true,
+            screen_mode: ScreenMode::Main,
+            use_alternate_screen: false,
+            pagination_format: Some("Page {page} of {total}"),
+            title_border: false,
+        }
+    }
+}
+
+/// Callbacks are shown as `Some("Fn")` when set.
+impl std::fmt::Debug for MenuProps<'_> {
+    #[allow(deprecated)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MenuProps")
+            .field("title", &self.title)
+            .field("title_prefix", &self.title_prefix)
+            .field("title_suffix", &self.title_suffix)
+            .field("message", &self.message)
+            .field("exit_on_action", &self.exit_on_action)
+            .field("multi_run_mode", &self.multi_run_mode)
+            .field("bg_color", &self.bg_color)
+            .field("fg_color", &self.fg_color)
+            .field("title_color", &self.title_color)
+            .field("selected_color", &self.selected_color)
+            .field("msg_color", &self.msg_color)
+            .field("disabled_color", &self.disabled_color)
+            .field("page_change_callback", &self.page_change_callback.as_ref().map(|_| "Fn"))
+            .field("key_repeat_delay_ms", &self.key_repeat_delay_ms)
+            .field("key_repeat_rate_ms", &self.key_repeat_rate_ms)
+            .field("multi_select", &self.multi_select)
+            .field("check_char", &self.check_char)
+            .field("uncheck_char", &self.uncheck_char)
+            .field("separator_char", &self.separator_char)
+            .field("wrap_selection", &self.wrap_selection)
+            .field("page_size", &self.page_size)
+            .field("key_bindings", &self.key_bindings)
+            .field("no_color", &self.no_color)
+            .field("max_label_width", &self.max_label_width)
+            .field("color_support", &self.color_support)
+            .field("border_style", &self.border_style)
+            .field("border_color", &self.border_color)
+            .field("compact", &self.compact)
+            .field("columns", &self.columns)
+            .field("match_color", &self.match_color)
+            .field("show_shortcuts", &self.show_shortcuts)
+            .field("remember_position", &self.remember_position)
+            .field("selection_prefix", &self.selection_prefix)
+            .field("normal_prefix", &self.normal_prefix)
+            .field("bold_selection", &self.bold_selection)
+            .field("header_renderer", &self.header_renderer.as_ref().map(|_| "Fn"))
+            .field("footer_renderer", &self.footer_renderer.as_ref().map(|_| "Fn"))
+            .field("show_scrollbar", &self.show_scrollbar)
+            .field("layout", &self.layout)
+            .field("separator_str", &self.separator_str)
+            .field("title_align", &self.title_align)
+            .field("msg_align", &self.msg_align)
+            .field("menu_align", &self.menu_align)
+            .field("menu_valign", &self.menu_valign)
+            .field("breadcrumb", &self.breadcrumb)
+            .field("show_key_hints", &self.show_key_hints)
+            .field("vertical_padding", &self.vertical_padding)
+            .field("horizontal_padding", &self.horizontal_padding)
+            .field("show_loading", &self.show_loading)
+            .field("render_to_stderr", &self.render_to_stderr)
+            .field("wrap_mode", &self.wrap_mode)
+            .field("clear_on_show", &self.clear_on_show)
+            .field("clear_on_exit", &self.clear_on_exit)
+            .field("screen_mode", &self.screen_mode)
+            .field("use_alternate_screen", &self.use_alternate_screen)
+            .field("pagination_format", &self.pagination_format)
+            .field("title_border", &self.title_border)
+            .finish()
+    }
+}
+
+/// Collects the titles of nested menus for their breadcrumb bars. Each menu passes a trail
+/// extended with its own title to the menus opened from its options.
+///
+/// ```
+/// # use console_menu::{BreadcrumbBuilder, Key, Menu, MenuOption, MenuProps};
+/// # use console_menu::testing::MockTerm;
+/// let crumbs = BreadcrumbBuilder::new().child("Main").child("Settings");
+/// let colors_menu = move || {
+///     let term = MockTerm::new(vec![Key::Escape]);
+///     let mut menu = Menu::new(vec![MenuOption::new("red", || {})], MenuProps {
+///         title: "Colors",
+///         ..MenuProps::default()
+///     }.with_breadcrumb(crumbs.build()));
+///     menu.show_on_term(&term);
+///     term.output()
+/// };
+///
+/// let output = colors_menu();
+/// assert!(output.contains("Main > Settings"));
+/// assert!(output.contains("Colors"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BreadcrumbBuilder {
+    crumbs: Vec<String>,
+}
+
+impl BreadcrumbBuilder {
+    /// Creates an empty trail for a top level menu.
+    pub fn new() -> Self {
+        BreadcrumbBuilder::default()
+    }
+
+    /// Returns the trail for a menu opened from the menu titled `title`, leaving this one
+    /// unchanged so it can be reused by other options of the same menu.
+    pub fn child(&self, title: &str) -> Self {
+        let mut crumbs = self.crumbs.clone();
+        crumbs.push(title.to_owned());
+        BreadcrumbBuilder { crumbs }
+    }
+
+    /// Returns the titles in the trail, for `MenuProps::with_breadcrumb`.
+    pub fn build(&self) -> Vec<String> {
+        self.crumbs.clone()
+    }
+}
+
+/// Builds a `MenuProps` through method chaining, validating the result.
+///
+/// Created with `MenuProps::builder()`. Color setters take either an 8-bit color or a `ColorMode`,
+/// and setters for optional colors take the color directly.
+///
+/// ```
+/// # use console_menu::{color, ColorMode, Menu, MenuError, MenuOption, MenuProps};
+/// let props = MenuProps::builder().fg_color(color::BLUE).bg_color(color::BLUE).build();
+/// assert!(matches!(props, Err(MenuError::ColorClash(ColorMode::Color256(color::BLUE)))));
+///
+/// let props = MenuProps::builder().title("My Menu").build().unwrap();
+/// let menu = Menu::new(vec![MenuOption::new("option", || {})], props);
+/// ```
+pub struct MenuPropsBuilder<'a> {
+    props: MenuProps<'a>,
+}
+
+impl<'a> MenuPropsBuilder<'a> {
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.props.title = title;
+        self
+    }
+
+    pub fn title_prefix(mut self, prefix: &'a str) -> Self {
+        self.props.title_prefix = prefix;
+        self
+    }
+
+    pub fn title_suffix(mut self, suffix: &'a str) -> Self {
+        self.props.title_suffix = suffix;
+        self
+    }
+
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.props.message = message;
+        self
+    }
+
+    pub fn exit_on_action(mut self, exit_on_action: bool) -> Self {
+        self.props.exit_on_action = exit_on_action;
+        self
+    }
+
+    pub fn multi_run_mode(mut self, multi_run_mode: bool) -> Self {
+        self.props.multi_run_mode = multi_run_mode;
+        self
+    }
+
+    pub fn bg_color(mut self, color: impl Into<ColorMode>) -> Self {
+        self.props.bg_color = color.into();
+        self
+    }
+
+    pub fn fg_color(mut self, color: impl Into<ColorMode>) -> Self {
+        self.props.fg_color = color.into();
+        self
+    }
+
+    pub fn title_color(mut self, color: impl Into<ColorMode>) -> Self {
+        self.props.title_color = Some(color.into());
+        self
+    }
+
+    pub fn selected_color(mut self, color: impl Into<ColorMode>) -> Self {
+        self.props.selected_color = Some(color.into());
+        self
+    }
+
+    pub fn msg_color(mut self, color: impl Into<ColorMode>) -> Self {
+        self.props.msg_color = Some(color.into());
+        self
+    }
+
+    pub fn disabled_color(mut self, color: impl Into<ColorMode>) -> Self {
+        self.props.disabled_color = Some(color.into());
+        self
+    }
+
+    pub fn page_change_callback(mut self, f: impl Fn(usize) + 'static) -> Self {
+        self.props.page_change_callback = Some(Rc::new(f));
+        self
+    }
+
+    pub fn key_repeat_delay_ms(mut self, delay: u64) -> Self {
+        self.props.key_repeat_delay_ms = delay;
+        self
+    }
+
+    pub fn key_repeat_rate_ms(mut self, rate: u64) -> Self {
+        self.props.key_repeat_rate_ms = rate;
+        self
+    }
+
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.props.multi_select = multi_select;
+        self
+    }
+
+    pub fn check_char(mut self, check_char: char) -> Self {
+        self.props.check_char = check_char;
+        self
+    }
+
+    pub fn uncheck_char(mut self, uncheck_char: char) -> Self {
+        self.props.uncheck_char = uncheck_char;
+        self
+    }
+
+    pub fn separator_char(mut self, separator_char: char) -> Self {
+        self.props.separator_char = separator_char;
+        self
+    }
+
+    #[deprecated(since = "0.4.0", note = "use `wrap_mode(WrapMode::WrapPages)` instead")]
+    #[allow(deprecated)]
+    pub fn wrap_selection(mut self, wrap_selection: bool) -> Self {
+        self.props.wrap_selection = wrap_selection;
+        self
+    }
+
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.props.page_size = Some(page_size);
+        self
+    }
+
+    pub fn key_bindings(mut self, key_bindings: KeyBindings) -> Self {
+        self.props.key_bindings = key_bindings;
+        self
+    }
+
+    pub fn no_color(mut self, no_color: bool) -> Self {
+        self.props.no_color = no_color;
+        self
+    }
+
+    pub fn max_label_width(mut self, max_label_width: usize) -> Self {
+        self.props.max_label_width = Some(max_label_width);
+        self
+    }
+
+    pub fn color_support(mut self, color_support: ColorSupport) -> Self {
+        self.props.color_support = Some(color_support);
+        self
+    }
+
+    pub fn border_style(mut self, border_style: BorderStyle) -> Self {
+        self.props.border_style = border_style;
+        self
+    }
+
+    pub fn border_color(mut self, border_color: impl Into<ColorMode>) -> Self {
+        self.props.border_color = Some(border_color.into());
+        self
+    }
+
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.props.compact = compact;
+        self
+    }
+
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.props.columns = Some(columns);
+        self
+    }
+
+    pub fn match_color(mut self, match_color: impl Into<ColorMode>) -> Self {
+        self.props.match_color = Some(match_color.into());
+        self
+    }
+
+    pub fn show_shortcuts(mut self, show_shortcuts: bool) -> Self {
+        self.props.show_shortcuts = show_shortcuts;
+        self
+    }
+
+    pub fn remember_position(mut self, remember_position: bool) -> Self {
+        self.props.remember_position = remember_position;
+        self
+    }
+
+    pub fn selection_prefix(mut self, selection_prefix: &'a str) -> Self {
+        self.props.selection_prefix = Some(selection_prefix);
+        self
+    }
+
+    pub fn normal_prefix(mut self, normal_prefix: &'a str) -> Self {
+        self.props.normal_prefix = Some(normal_prefix);
+        self
+    }
+
+    pub fn bold_selection(mut self, bold_selection: bool) -> Self {
+        self.props.bold_selection = bold_selection;
+        self
+    }
+
+    pub fn header_renderer(mut self, header_renderer: impl Fn() -> String + 'static) -> Self {
+        self.props.header_renderer = Some(Rc::new(header_renderer));
+        self
+    }
+
+    pub fn footer_renderer(mut self, footer_renderer: impl Fn() -> String + 'static) -> Self {
+        self.props.footer_renderer = Some(Rc::new(footer_renderer));
+        self
+    }
+
+    pub fn show_scrollbar(mut self, show_scrollbar: bool) -> Self {
+        self.props.show_scrollbar = show_scrollbar;
+        self
+    }
+
+    pub fn layout(mut self, layout: MenuLayout) -> Self {
+        self.props.layout = layout;
+        self
+    }
+
+    pub fn separator_str(mut self, separator_str: &'a str) -> Self {
+        self.props.separator_str = separator_str;
+        self
+    }
+
+    pub fn title_align(mut self, title_align: TitleAlign) -> Self {
+        self.props.title_align = title_align;
+        self
+    }
+
+    pub fn msg_align(mut self, msg_align: MessageAlign) -> Self {
+        self.props.msg_align = msg_align;
+        self
+    }
+
+    pub fn menu_align(mut self, menu_align: MenuAlign) -> Self {
+        self.props.menu_align = menu_align;
+        self
+    }
+
+    pub fn menu_valign(mut self, menu_valign: MenuVAlign) -> Self {
+        self.props.menu_valign = menu_valign;
+        self
+    }
+
+    pub fn breadcrumb(mut self, breadcrumb: Vec<String>) -> Self {
+        self.props.breadcrumb = Some(breadcrumb);
+        self
+    }
+
+    pub fn show_key_hints(mut self, show_key_hints: bool) -> Self {
+        self.props.show_key_hints = show_key_hints;
+        self
+    }
+
+    pub fn vertical_padding(mut self, vertical_padding: MenuPadding) -> Self {
+        self.props.vertical_padding = vertical_padding;
+        self
+    }
+
+    pub fn horizontal_padding(mut self, horizontal_padding: MenuPadding) -> Self {
+        self.props.horizontal_padding = horizontal_padding;
+        self
+    }
+
+    pub fn show_loading(mut self, show_loading: bool) -> Self {
+        self.props.show_loading = show_loading;
+        self
+    }
+
+    pub fn render_to_stderr(mut self, render_to_stderr: bool) -> Self {
+        self.props.render_to_stderr = render_to_stderr;
+        self
+    }
+
+    pub fn wrap_mode(mut self, wrap_mode: WrapMode) -> Self {
+        self.props.wrap_mode = wrap_mode;
+        self
+    }
+
+    pub fn clear_on_show(mut self, clear_on_show: bool) -> Self {
+        self.props.clear_on_show = clear_on_show;
+        self
+    }
+
+    pub fn clear_on_exit(mut self, clear_on_exit: bool) -> Self {
+        self.props.clear_on_exit = clear_on_exit;
+        self
+    }
+
+    pub fn screen_mode(mut self, screen_mode: ScreenMode) -> Self {
+        self.props.screen_mode = screen_mode;
+        self
+    }
+
+    #[deprecated(since = "0.4.0", note = "use `screen_mode(ScreenMode::Alternate)` instead")]
+    #[allow(deprecated)]
+    pub fn use_alternate_screen(mut self, use_alternate_screen: bool) -> Self {
+        self.props.use_alternate_screen = use_alternate_screen;
+        self
+    }
+
+    pub fn pagination_format(mut self, pagination_format: Option<&'a str>) -> Self {
+        self.props.pagination_format = pagination_format;
+        self
+    }
+
+    pub fn title_border(mut self, title_border: bool) -> Self {
+        self.props.title_border = title_border;
+        self
+    }
+
+    /// Returns the configured `MenuProps`, or an error if the background and foreground colors
+    /// are identical.
+    pub fn build(self) -> Result<MenuProps<'a>, MenuError> {
+        if self.props.bg_color == self.props.fg_color {
+            return Err(MenuError::ColorClash(self.props.fg_color));
+        }
+        Ok(self.props)
+    }
+}
+
+/// The keys used to control a `Menu`.
+///
+/// Each action can be triggered by any of several keys. Configure a subset of bindings using the
+/// defaults and struct update syntax, e.g. to drop the vim style keys:
+///
+/// ```
+/// # use console_menu::{Key, KeyBindings, MenuProps};
+/// let props = MenuProps {
+///     key_bindings: KeyBindings {
+///         up: vec![Key::ArrowUp],
+///         down: vec![Key::ArrowDown],
+///         left: vec![Key::ArrowLeft],
+///         right: vec![Key::ArrowRight],
+///         ..KeyBindings::default()
+///     },
+///     ..MenuProps::default()
+/// };
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeyBindings {
+    /// Selects the previous option.
+    pub up: Vec<Key>,
+    /// Selects the next option.
+    pub down: Vec<Key>,
+    /// Switches to the previous page, or moves to the previous column or option in menus laid
+    /// out in columns or horizontally.
+    pub left: Vec<Key>,
+    /// Switches to the next page, or moves to the next column or option in menus laid out in
+    /// columns or horizontally.
+    pub right: Vec<Key>,
+    /// Switches to the previous page in any layout, selecting the first option on it.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// let options = (0..7).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+    /// let mut menu = Menu::new(options, MenuProps { page_size: Some(3), columns: Some(3), ..MenuProps::default() });
+    /// let position = |menu: &Menu<i32>| (menu.snapshot().selected_page, menu.snapshot().selected_option);
+    ///
+    /// // a page at a time, even though right would move to the next column
+    /// menu.handle_key(Key::PageDown);
+    /// assert_eq!(position(&menu), (1, 3));
+    /// menu.handle_key(Key::ArrowDown);
+    /// menu.handle_key(Key::PageDown);
+    /// assert_eq!(position(&menu), (2, 6));
+    /// // the last page stays selected
+    /// menu.handle_key(Key::PageDown);
+    /// assert_eq!(position(&menu), (2, 6));
+    ///
+    /// menu.handle_key(Key::PageUp);
+    /// assert_eq!(position(&menu), (1, 3));
+    /// ```
+    ///
+    /// Menus with a single page are left as they are:
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::with_value("yes", true),
+    ///     MenuOption::with_value("no", false),
+    /// ], MenuProps::default());
+    /// menu.handle_key(Key::ArrowDown);
+    ///
+    /// for key in [Key::PageDown, Key::PageUp] {
+    ///     menu.handle_key(key);
+    ///     assert_eq!((menu.snapshot().selected_page, menu.snapshot().selected_option), (0, 1));
+    /// }
+    /// ```
+    pub page_up: Vec<Key>,
+    /// Switches to the next page in any layout, selecting the first option on it.
+    pub page_down: Vec<Key>,
+    /// Selects the first option.
+    pub first: Vec<Key>,
+    /// Selects the last option.
+    pub last: Vec<Key>,
+    /// Runs the selected option, or confirms the checked options in multi-select mode.
+    pub confirm: Vec<Key>,
+    /// Exits the menu.
+    pub exit: Vec<Key>,
+    /// Checks or unchecks the selected option in multi-select mode.
+    pub toggle: Vec<Key>,
+    /// Checks every option on the current page in multi-select mode.
+    pub check_page: Vec<Key>,
+    /// Unchecks all options in multi-select mode.
+    pub clear_checked: Vec<Key>,
+    /// Starts searching. While searching, typed characters filter the options to those whose
+    /// label contains the query, escape ends the search and enter confirms the selected option.
+    pub search: Vec<Key>,
+}
+
+impl KeyBindings {
+    fn command(&self, key: &Key, multi_select: bool) -> Option<Command> {
+        let multi_select_commands = [
+            (&self.toggle, Command::Toggle),
+            (&self.check_page, Command::CheckPage),
+            (&self.clear_checked, Command::ClearChecked),
+        ];
+        let commands = [
+            (&self.up, Command::Up),
+            (&self.down, Command::Down),
+            (&self.left, Command::Left),
+            (&self.right, Command::Right),
+            (&self.page_up, Command::PageUp),
+            (&self.page_down, Command::PageDown),
+            (&self.first, Command::First),
+            (&self.last, Command::Last),
+            (&self.confirm, Command::Confirm),
+            (&self.exit, Command::Exit),
+            (&self.search, Command::Search),
+        ];
+        multi_select_commands.into_iter().filter(|_| multi_select)
+            .chain(commands)
+            .find(|(keys, _)| keys.contains(key))
+            .map(|(_, command)| command)
+    }
+}
+
+/// ```
+/// # use console_menu::{Key, KeyBindings};
+/// # fn default() -> KeyBindings {
+/// KeyBindings {
+///     up: vec![Key::ArrowUp, Key::Char('k')],
+///     down: vec![Key::ArrowDown, Key::Char('j')],
+///     left: vec![Key::ArrowLeft, Key::Char('h'), Key::Char('b')],
+///     right: vec![Key::ArrowRight, Key::Char('l'), Key::Char('w')],
+///     page_up: vec![Key::PageUp],
+///     page_down: vec![Key::PageDown],
+///     first: vec![Key::Home],
+///     last: vec![Key::End],
+///     confirm: vec![Key::Enter],
+///     exit: vec![Key::Escape, Key::Char('q'), Key::Backspace],
+///     toggle: vec![Key::Char(' ')],
+///     check_page: vec![Key::Char('a')],
+///     clear_checked: vec![Key::Char('c')],
+///     search: vec![Key::Char('/')],
+/// }
+/// # }
+/// ```
+impl Default for KeyBindings {
+    fn default() -> KeyBindings {
+        KeyBindings {
+            up: vec![Key::ArrowUp, Key::Char('k')],
+            down: vec![Key::ArrowDown, Key::Char('j')],
+            left: vec![Key::ArrowLeft, Key::Char('h'), Key::Char('b')],
+            right: vec![Key::ArrowRight, Key::Char('l'), Key::Char('w')],
+            page_up: vec![Key::PageUp],
+            page_down: vec![Key::PageDown],
+            first: vec![Key::Home],
+            last: vec![Key::End],
+            confirm: vec![Key::Enter],
+            exit: vec![Key::Escape, Key::Char('q'), Key::Backspace],
+            toggle: vec![Key::Char(' ')],
+            check_page: vec![Key::Char('a')],
+            clear_checked: vec![Key::Char('c')],
+            search: vec![Key::Char('/')],
+        }
+    }
+}
+
+enum Command {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    Confirm,
+    Exit,
+    Toggle,
+    CheckPage,
+    ClearChecked,
+    Search,
+}
+
+/// What happened after a key was passed to `Menu::handle_key`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    /// The selection, page, search query or checked options may have changed.
+    Navigate,
+    /// The option at the given index was selected with enter or its shortcut, or the checked
+    /// options were confirmed in multi-select mode.
+    Select(usize),
+    /// The user asked to exit the menu.
+    Exit,
+    /// The key isn't bound to anything.
+    NoOp,
+}
+
+impl MenuAction {
+    /// Returns true if the menu should be redrawn to reflect its new state.
+    pub fn requires_redraw(&self) -> bool {
+        matches!(self, MenuAction::Navigate)
+    }
+}
+
+/// Errors produced when configuring or displaying a `Menu`.
+#[derive(Debug)]
+pub enum MenuError {
+    /// The background and foreground colors are the same, which would make text unreadable.
+    ColorClash(ColorMode),
+    /// Writing to the terminal or reading a key from it failed.
+    TerminalError(io::Error),
+    /// The menu has no options at all.
+    ///
+    /// Menus never return this themselves: a menu given no options shows a single "exit" option
+    /// instead, and removing every option puts it back. It's kept for code that builds options
+    /// from elsewhere, such as a config file, to report having none alongside the menu's own
+    /// errors. Menus whose options are all disabled, separators, or section headers can also be
+    /// shown, and exited with esc.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let mut menu: Menu = Menu::new(vec![], MenuProps::default());
+    /// assert_eq!(menu.option(0).unwrap().label, "exit");
+    /// assert!(matches!(menu.try_show_on_term(MockTerm::new(vec![Key::Enter])), Ok(Some(0))));
+    ///
+    /// let mut menu: Menu = Menu::new(vec![
+    ///     MenuOption::disabled("no saves found"),
+    /// ], MenuProps::default());
+    /// assert!(matches!(menu.try_show_on_term(MockTerm::new(vec![Key::Escape])), Ok(None)));
+    /// ```
+    EmptyOptions,
+    /// The option set to be selected initially no longer exists, as options were removed after
+    /// `set_initial_selection` was called.
+    InvalidIndex(usize),
+}
+
+impl std::fmt::Display for MenuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MenuError::ColorClash(color) => write!(f, "background and foreground colors are both {}", color),
+            MenuError::TerminalError(err) => write!(f, "terminal error: {}", err),
+            MenuError::EmptyOptions => write!(f, "menu has no options"),
+            MenuError::InvalidIndex(index) => write!(f, "option index {} is out of bounds", index),
+        }
+    }
+}
+
+impl std::error::Error for MenuError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MenuError::TerminalError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MenuError {
+    fn from(err: io::Error) -> Self {
+        MenuError::TerminalError(err)
+    }
+}
+
+/// A terminal a `Menu` can be displayed on.
+///
+/// Implemented for `console::Term`. Implement it for other types to display menus elsewhere, or
+/// to drive a menu with scripted keys and capture its output. `testing::MockTerm` is a ready-made
+/// scripted terminal.
+///
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use std::io;
+/// # use console_menu::{Key, Menu, MenuOption, MenuProps, TerminalOutput};
+/// struct ScriptedTerm {
+///     keys: RefCell<Vec<Key>>,
+///     output: RefCell<String>,
+/// }
+///
+/// impl TerminalOutput for ScriptedTerm {
+///     fn write_str(&self, s: &str) -> io::Result<()> {
+///         self.output.borrow_mut().push_str(s);
+///         Ok(())
+///     }
+///
+///     fn size(&self) -> (u16, u16) {
+///         (24, 80)
+///     }
+///
+///     fn read_key(&self) -> io::Result<Key> {
+///         Ok(self.keys.borrow_mut().remove(0))
+///     }
+/// }
+///
+/// let term = ScriptedTerm {
+///     keys: RefCell::new(vec![Key::ArrowDown, Key::Enter]),
+///     output: RefCell::new(String::new()),
+/// };
+/// let mut menu = Menu::new(vec![
+///     MenuOption::new("first", || {}),
+///     MenuOption::new("second", || {}),
+/// ], MenuProps { title: "My Menu", ..MenuProps::default() });
+///
+/// assert_eq!(menu.show_on_term(&term), Some(1));
+/// assert!(term.output.borrow().contains("My Menu"));
+/// ```
+pub trait TerminalOutput {
+    fn write_str(&self, s: &str) -> io::Result<()>;
+
+    /// Returns the terminal size as (rows, columns).
+    fn size(&self) -> (u16, u16);
+
+    /// Blocks until a key is pressed. Ctrl+C should be reported as `Key::CtrlC` rather than
+    /// interrupting the process, so the menu can restore the terminal before exiting.
+    fn read_key(&self) -> io::Result<Key>;
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.write_str(&format!("{}\n", s))
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        self.write_str("\x1b[?25l")
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        self.write_str("\x1b[?25h")
+    }
+
+    /// Clears the screen and moves the cursor to the top left corner, before every frame of the
+    /// menu is drawn.
+    ///
+    /// ```
+    /// # use std::cell::{Cell, RefCell};
+    /// # use std::io;
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps, TerminalOutput};
+    /// struct CountingTerm {
+    ///     clears: Cell<usize>,
+    ///     output: RefCell<String>,
+    /// }
+    ///
+    /// impl TerminalOutput for CountingTerm {
+    ///     fn write_str(&self, s: &str) -> io::Result<()> {
+    ///         self.output.borrow_mut().push_str(s);
+    ///         Ok(())
+    ///     }
+    /// #
+    /// #     fn size(&self) -> (u16, u16) {
+    /// #         (24, 80)
+    /// #     }
+    /// #
+    /// #     fn read_key(&self) -> io::Result<Key> {
+    /// #         Ok(Key::Escape)
+    /// #     }
+    ///
+    ///     fn clear_screen(&self) -> io::Result<()> {
+    ///         self.clears.set(self.clears.get() + 1);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let term = CountingTerm { clears: Cell::new(0), output: RefCell::new(String::new()) };
+    /// let mut menu = Menu::new(vec![MenuOption::new("ok", || {})], MenuProps::default());
+    /// menu.show_on_term(&term);
+    ///
+    /// // one clear for the only frame, and one on exit
+    /// assert_eq!(term.clears.get(), 2);
+    /// assert!(!term.output.borrow().contains("\x1b[J"));
+    /// ```
+    fn clear_screen(&self) -> io::Result<()> {
+        self.write_str(CLEAR_SCREEN)
+    }
+
+    /// Returns a handle to the same terminal that can be used from another thread, or None if
+    /// there isn't one. Menus read keys through it on a background thread so they can redraw as
+    /// soon as the terminal is resized, and menus with `show_loading` set animate the loading
+    /// indicator through it while an action runs. Without one, resizes are picked up on the next
+    /// key press and the indicator is drawn once without animating.
+    fn clone_for_thread(&self) -> Option<Box<dyn TerminalOutput + Send>> {
+        None
+    }
+}
+
+impl TerminalOutput for Term {
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        Term::write_str(self, s)
+    }
+
+    fn size(&self) -> (u16, u16) {
+        Term::size(self)
+    }
+
+    fn read_key(&self) -> io::Result<Key> {
+        Term::read_key_raw(self)
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        Term::write_line(self, s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Term::flush(self)
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        Term::hide_cursor(self)
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        Term::show_cursor(self)
+    }
+
+    // Windows consoles without escape sequence support are cleared through the console API.
+    // Elsewhere the screen is erased from the top left corner, as some terminals push the whole
+    // screen into the scrollback on every `Term::clear_screen`.
+    #[cfg(windows)]
+    fn clear_screen(&self) -> io::Result<()> {
+        Term::clear_screen(self)
+    }
+
+    fn clone_for_thread(&self) -> Option<Box<dyn TerminalOutput + Send>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+impl<T: TerminalOutput + ?Sized> TerminalOutput for &T {
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        (**self).write_str(s)
+    }
+
+    fn size(&self) -> (u16, u16) {
+        (**self).size()
+    }
+
+    fn read_key(&self) -> io::Result<Key> {
+        (**self).read_key()
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        (**self).write_line(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        (**self).flush()
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        (**self).hide_cursor()
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        (**self).show_cursor()
+    }
+
+    fn clear_screen(&self) -> io::Result<()> {
+        (**self).clear_screen()
+    }
+
+    fn clone_for_thread(&self) -> Option<Box<dyn TerminalOutput + Send>> {
+        (**self).clone_for_thread()
+    }
+}
+
+/// An element in a `Menu`.
+///
+/// Consists of a label, a callback, and a value returned from `Menu::show` when the option is
+/// selected. Callbacks can be any function, including functions that call nested menus:
+///
+/// ```
+/// # use console_menu::{Menu, MenuOption, MenuProps};
+/// // a menu built from an empty list needs its type spelled out, as there are no options to
+/// // infer the value type from
+/// let mut nested_menu: Menu = Menu::new(vec![], MenuProps::default());
+/// let show_nested = MenuOption::new("show nested menu", move || nested_menu.show());
+/// ```
+pub struct MenuOption<T = ()> {
+    pub label: String,
+    pub value: Option<T>,
+    pub action: Box<dyn FnMut()>,
+    /// Whether the option is checked in a multi-select menu, or switched on for toggle options.
+    /// Can be set before showing the menu to pre-check options.
+    pub checked: bool,
+    /// Disabled options are displayed but skipped during navigation, and can't be selected.
+    pub disabled: bool,
+    /// Separators are drawn as a horizontal line across the menu and skipped during navigation.
+    pub separator: bool,
+    /// Section headers are drawn in bold in the title color and skipped during navigation. See
+    /// `MenuSection`.
+    pub section: bool,
+    /// Optional text displayed on a second line below the label. Descriptions aren't shown when
+    /// the menu has several columns.
+    pub description: Option<String>,
+    /// Optional text displayed in place of the menu's message while the option is selected.
+    pub tooltip: Option<String>,
+    /// Optional function called whenever the option becomes selected while navigating. It runs
+    /// before the menu is redrawn, so it should return quickly.
+    pub on_select: Option<Box<dyn Fn()>>,
+    /// Optional function called when the option is confirmed, just before its action runs.
+    pub on_confirm: Option<Box<dyn Fn()>>,
+    /// Makes the option a text field, edited in place when confirmed. See
+    /// `MenuOption::text_input`.
+    pub text_input: Option<TextInput>,
+    /// Makes the option a toggle, switching `checked` when confirmed and calling this function
+    /// with the new state. See `MenuOption::toggle`.
+    pub on_toggle: Option<Box<dyn FnMut(bool)>>,
+    /// Data attached to the option by the caller, to look up once the option is selected. The
+    /// menu never reads it. See `MenuOption::with_meta`.
+    pub metadata: HashMap<String, Box<dyn Any + Send + Sync>>,
+    /// Optional color the label is drawn in instead of the menu's foreground color. The menu's
+    /// selected color takes priority while the option is selected.
+    pub color: Option<ColorMode>,
+    /// Optional background color behind the label, instead of the menu's background color.
+    pub bg_color: Option<ColorMode>,
+    /// Optional function returning the text to display in place of the label, called every time
+    /// the menu is drawn. It should return quickly. The menu is sized for `label`, so longer text
+    /// is cut short and ends in `…`.
+    pub label_refresh: Option<Box<dyn Fn() -> String>>,
+    /// Makes the option part of a group of radio buttons, of which only one is checked. See
+    /// `MenuOption::radio`.
+    pub radio: Option<RadioButton>,
+    /// Text styles the label is always drawn with. The selected option is drawn in bold on top
+    /// of these.
+    pub style: OptionStyle,
+}
+
+/// Text styles applied to the label of an option, see `MenuOption::with_style`. Styles aren't
+/// drawn when `MenuProps::no_color` is set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OptionStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+
+/// The text field of an option created with `MenuOption::text_input`.
+pub struct TextInput {
+    /// Text shown dimmed after the label while the field isn't being edited.
+    pub placeholder: String,
+    /// The most characters that can be typed into the field.
+    pub max_chars: usize,
+    /// Called with the typed text when it's submitted with enter.
+    pub on_submit: Box<dyn FnMut(String)>,
+}
+
+/// The group and value of an option created with `MenuOption::radio`.
+pub struct RadioButton {
+    /// Options with the same group id in a menu form one group.
+    pub group_id: String,
+    /// Passed to `on_select` when the option is checked.
+    pub value: String,
+    /// Called with `value` when the option is checked by confirming it.
+    pub on_select: Box<dyn FnMut(&str)>,
+}
+
+impl MenuOption {
+    /// Creates an option that runs `action` when selected. Any value returned by the action is
+    /// discarded.
+    pub fn new<R: 'static>(label: &str, mut action: impl FnMut() -> R + 'static) -> Self {
+        Self {
+            label: label.to_owned(),
+            value: Some(()),
+            action: Box::new(move || {
+                action();
+            }),
+            checked: false,
+            disabled: false,
+            separator: false,
+            section: false,
+            description: None,
+            tooltip: None,
+            on_select: None,
+            on_confirm: None,
+            text_input: None,
+            on_toggle: None,
+            metadata: HashMap::new(),
+            color: None,
+            bg_color: None,
+            label_refresh: None,
+            radio: None,
+            style: OptionStyle::default(),
+        }
+    }
+
+    /// Creates an option that runs `action` when selected, with a description shown below the
+    /// label.
+    ///
+    /// ```
+    /// # use console_menu::MenuOption;
+    /// let option = MenuOption::with_description("git clone", "Download a remote repository", || {});
+    /// ```
+    pub fn with_description<R: 'static>(label: &str, description: &str, action: impl FnMut() -> R + 'static) -> Self {
+        Self {
+            description: Some(description.to_owned()),
+            ..Self::new(label, action)
+        }
+    }
+
+    /// Creates an option that runs `action` when selected, showing `tooltip` in place of the
+    /// menu's message while the option is selected.
+    ///
+    /// ```
+    /// # use console_menu::MenuOption;
+    /// let option = MenuOption::with_tooltip("deploy", "Pushes the current build to production", || {});
+    /// ```
+    pub fn with_tooltip<R: 'static>(label: &str, tooltip: &str, action: impl FnMut() -> R + 'static) -> Self {
+        Self {
+            tooltip: Some(tooltip.to_owned()),
+            ..Self::new(label, action)
+        }
+    }
+
+    /// Creates an option showing live data, whose label is replaced by the text `refresh` returns
+    /// every time the menu is drawn. The menu is sized for `initial_label`.
+    ///
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let volume = Cell::new(0);
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::with_live_label("volume: 100%", move || {
+    ///         volume.set(volume.get() + 10);
+    ///         format!("volume: {}%", volume.get())
+    ///     }, || {}),
+    /// ], MenuProps::default());
+    ///
+    /// let term = MockTerm::new(vec![Key::ArrowDown, Key::Escape]);
+    /// menu.show_on_term(&term);
+    /// let output = term.output();
+    /// let first = output.find("volume: 10%").unwrap();
+    /// assert!(output[first..].contains("volume: 20%"));
+    /// ```
+    pub fn with_live_label<R: 'static>(
+        initial_label: &str,
+        refresh: impl Fn() -> String + 'static,
+        action: impl FnMut() -> R + 'static,
+    ) -> Self {
+        Self {
+            label_refresh: Some(Box::new(refresh)),
+            ..Self::new(initial_label, action)
+        }
+    }
+
+    /// Creates an option that collects a line of text. Confirming it starts editing the text in
+    /// place: typed characters are added, including ones bound to navigation, backspace deletes,
+    /// enter passes the text to `on_submit` and escape cancels. At most 32 characters can be
+    /// typed unless changed with `max_chars`.
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let name = Rc::new(RefCell::new(String::new()));
+    /// let submitted = Rc::clone(&name);
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::text_input("name", "type a name", move |text| *submitted.borrow_mut() = text),
+    /// ], MenuProps::default());
+    ///
+    /// let mut keys = vec![Key::Enter];
+    /// keys.extend("hello".chars().map(Key::Char));
+    /// keys.extend([Key::Enter, Key::Escape]);
+    /// let term = MockTerm::new(keys);
+    /// menu.show_on_term(&term);
+    /// assert_eq!(*name.borrow(), "hello");
+    /// ```
+    pub fn text_input(label: &str, placeholder: &str, on_submit: impl FnMut(String) + 'static) -> Self {
+        Self {
+            text_input: Some(TextInput {
+                placeholder: placeholder.to_owned(),
+                max_chars: 32,
+                on_submit: Box::new(on_submit),
+            }),
+            ..Self::new(label, || {})
+        }
+    }
+
+    /// Creates an option that switches between on and off, showing its state in a checkbox before
+    /// the label. Confirming it switches the state and calls `on_change` with the new one, without
+    /// exiting the menu.
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let changes = Rc::new(RefCell::new(vec![]));
+    /// let recorded = Rc::clone(&changes);
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::toggle("dark mode", false, move |on| recorded.borrow_mut().push(on)),
+    /// ], MenuProps { check_char: '✓', no_color: true, ..MenuProps::default() });
+    ///
+    /// let term = MockTerm::new(vec![Key::Enter, Key::Enter, Key::Escape]);
+    /// menu.show_on_term(&term);
+    /// assert_eq!(*changes.borrow(), [true, false]);
+    ///
+    /// let output = term.output();
+    /// let on = output.find("[✓] dark mode").unwrap();
+    /// assert!(output[..on].contains("[ ] dark mode"));
+    /// assert!(output[on..].contains("[ ] dark mode"));
+    /// ```
+    pub fn toggle(label: &str, initial: bool, on_change: impl FnMut(bool) + 'static) -> Self {
+        Self {
+            checked: initial,
+            on_toggle: Some(Box::new(on_change)),
+            ..Self::new(label, || {})
+        }
+    }
+
+    /// Creates a radio button. Of all the options in a menu with the same `group_id`, only one is
+    /// checked at a time. Confirming an unchecked one checks it, unchecks the rest of the group and
+    /// calls `on_select` with its `value`, without exiting the menu. Set `checked` to check an
+    /// option up front.
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let picked = Rc::new(RefCell::new(vec![]));
+    /// let options = ["small", "medium", "large"].map(|size| {
+    ///     let picked = Rc::clone(&picked);
+    ///     MenuOption::radio(size, "size", size, move |value| picked.borrow_mut().push(value.to_owned()))
+    /// });
+    /// let mut menu = Menu::new(options.into(), MenuProps { no_color: true, ..MenuProps::default() });
+    ///
+    /// // check "small", then "medium", then confirm "medium" again
+    /// let term = MockTerm::new(vec![Key::Enter, Key::ArrowDown, Key::Enter, Key::Enter, Key::Escape]);
+    /// menu.show_on_term(&term);
+    /// assert_eq!(*picked.borrow(), ["small", "medium"]);
+    /// assert!(!menu.option(0).unwrap().is_checked());
+    /// assert!(menu.option(1).unwrap().is_checked());
+    ///
+    /// let output = term.output();
+    /// let last_frame = &output[output.rfind("small").unwrap() - 4..];
+    /// assert!(last_frame.contains("( ) small") && last_frame.contains("(•) medium"));
+    /// ```
+    pub fn radio(label: &str, group_id: &str, value: &str, on_select: impl FnMut(&str) + 'static) -> Self {
+        Self {
+            radio: Some(RadioButton {
+                group_id: group_id.to_owned(),
+                value: value.to_owned(),
+                on_select: Box::new(on_select),
+            }),
+            ..Self::new(label, || {})
+        }
+    }
+}
+
+impl<T> MenuOption<T> {
+    /// Creates an option with no action that returns `value` from `Menu::show` when selected.
+    ///
+    /// ```
+    /// # use console_menu::MenuOption;
+    /// let option = MenuOption::with_value("small", 8u32);
+    /// ```
+    pub fn with_value(label: &str, value: T) -> Self {
+        Self {
+            label: label.to_owned(),
+            value: Some(value),
+            action: Box::new(|| {}),
+            checked: false,
+            disabled: false,
+            separator: false,
+            section: false,
+            description: None,
+            tooltip: None,
+            on_select: None,
+            on_confirm: None,
+            text_input: None,
+            on_toggle: None,
+            metadata: HashMap::new(),
+            color: None,
+            bg_color: None,
+            label_refresh: None,
+            radio: None,
+            style: OptionStyle::default(),
+        }
+    }
+
+    /// Creates a disabled option with no action or value, for displaying options that are
+    /// currently unavailable.
+    ///
+    /// ```
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let menu = Menu::new(vec![
+    ///     MenuOption::disabled("save"),
+    ///     MenuOption::new("quit", || {}),
+    /// ], MenuProps::default());
+    /// ```
+    pub fn disabled(label: &str) -> Self {
+        Self {
+            label: label.to_owned(),
+            disabled: true,
+            ..Self::default()
+        }
+    }
+
+    /// Creates a separator, drawn as a horizontal line to visually group the options around it.
+    ///
+    /// ```
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let menu = Menu::new(vec![
+    ///     MenuOption::new("new", || {}),
+    ///     MenuOption::new("open", || {}),
+    ///     MenuOption::separator(),
+    ///     MenuOption::new("quit", || {}),
+    /// ], MenuProps::default());
+    /// ```
+    pub fn separator() -> Self {
+        Self {
+            label: String::new(),
+            separator: true,
+            ..Self::default()
+        }
+    }
+
+    /// Enables or disables the option.
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+    }
+
+    /// Returns whether the option is checked, or switched on for toggle options.
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Switches the option between checked and unchecked. For toggle options, `on_toggle` is
+    /// called with the new state, as when the option is confirmed.
+    pub fn toggle_checked_state(&mut self) {
+        self.checked = !self.checked;
+        if let Some(on_toggle) = &mut self.on_toggle {
+            on_toggle(self.checked);
+        }
+    }
+
+    /// Sets `on_select`.
+    ///
+    /// ```
+    /// # use console_menu::MenuOption;
+    /// let option = MenuOption::new("report.txt", || {})
+    ///     .on_select(|| eprintln!("previewing report.txt"));
+    /// ```
+    pub fn on_select(mut self, f: impl Fn() + 'static) -> Self {
+        self.on_select = Some(Box::new(f));
+        self
+    }
+
+    /// Sets `on_confirm`.
+    pub fn on_confirm(mut self, f: impl Fn() + 'static) -> Self {
+        self.on_confirm = Some(Box::new(f));
+        self
+    }
+
+    /// Attaches `value` to the option under `key`, replacing any value already stored there.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("small", || {}).with_meta("size", 8u32),
+    ///     MenuOption::new("custom", || {}).with_meta("path", "/etc/custom.conf".to_owned()),
+    /// ], MenuProps::default());
+    ///
+    /// let term = MockTerm::new(vec![Key::ArrowDown, Key::Enter]);
+    /// let index = menu.run_once_on_term(&term).unwrap();
+    /// let option = menu.option(index).unwrap();
+    /// assert_eq!(option.get_meta::<String>("path").map(String::as_str), Some("/etc/custom.conf"));
+    /// assert_eq!(option.get_meta::<u32>("path"), None);
+    /// assert_eq!(menu.option(0).unwrap().get_meta::<u32>("size"), Some(&8));
+    /// ```
+    pub fn with_meta(mut self, key: &str, value: impl Any + Send + Sync + 'static) -> Self {
+        self.metadata.insert(key.to_owned(), Box::new(value));
+        self
+    }
+
+    /// Sets `color`, for example to draw dangerous options in red.
+    ///
+    /// ```
+    /// # use console_menu::{color, Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let term = MockTerm::new(vec![Key::Escape]);
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("delete", || {}).with_color(color::RED),
+    ///     MenuOption::new("proceed", || {}),
+    /// ], MenuProps::default());
+    /// menu.set_initial_selection(1);
+    /// menu.show_on_term(&term);
+    ///
+    /// let output = term.output();
+    /// for line in output.lines().filter(|line| line.contains("\x1b[38;5;160m")) {
+    ///     assert!(line.contains("delete") && !line.contains("proceed"));
+    /// }
+    /// assert!(output.lines().any(|line| line.contains("\x1b[38;5;160mdelete")));
+    /// ```
+    pub fn with_color(mut self, color: impl Into<ColorMode>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Sets `bg_color`.
+    pub fn with_bg_color(mut self, color: impl Into<ColorMode>) -> Self {
+        self.bg_color = Some(color.into());
+        self
+    }
+
+    /// Sets `style`.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps, OptionStyle};
+    /// # use console_menu::testing::MockTerm;
+    /// let term = MockTerm::new(vec![Key::Escape]);
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("home", || {}),
+    ///     MenuOption::new("docs", || {}).with_style(OptionStyle { underline: true, ..OptionStyle::default() }),
+    ///     MenuOption::new("about", || {}),
+    /// ], MenuProps::default());
+    /// menu.show_on_term(&term);
+    ///
+    /// let output = term.output();
+    /// assert!(output.lines().any(|line| line.contains("\x1b[4mdocs\x1b[24m")));
+    /// assert!(output.lines().filter(|line| line.contains("\x1b[4m")).all(|line| line.contains("docs")));
+    /// ```
+    pub fn with_style(mut self, style: OptionStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Returns the value attached under `key`, or None if there is none or it isn't a `M`.
+    pub fn get_meta<M: 'static>(&self, key: &str) -> Option<&M> {
+        self.metadata.get(key)?.downcast_ref()
+    }
+
+    /// Copies everything about the option except its value and callbacks, for inspection. The
+    /// metadata values can't be copied, so only their keys are kept.
+    pub fn clone_without_action(&self) -> MenuOptionTemplate {
+        let mut metadata_keys: Vec<String> = self.metadata.keys().cloned().collect();
+        metadata_keys.sort();
+        MenuOptionTemplate {
+            label: self.label.clone(),
+            checked: self.checked,
+            disabled: self.disabled,
+            separator: self.separator,
+            section: self.section,
+            description: self.description.clone(),
+            tooltip: self.tooltip.clone(),
+            color: self.color,
+            bg_color: self.bg_color,
+            style: self.style,
+            metadata_keys,
+        }
+    }
+
+    /// Sets the most characters that can be typed into a text input option. Has no effect on
+    /// other options.
+    pub fn max_chars(mut self, max_chars: usize) -> Self {
+        if let Some(text_input) = &mut self.text_input {
+            text_input.max_chars = max_chars;
+        }
+        self
+    }
+}
+
+/// An option labelled "exit" with no action and no value.
+///
+/// ```
+/// # use std::collections::HashMap;
+/// # use console_menu::{MenuOption, OptionStyle};
+/// # fn default() -> MenuOption<u32> {
+/// MenuOption {
+///     label: "exit".to_owned(),
+///     value: None,
+///     action: Box::new(|| {}),
+///     checked: false,
+///     disabled: false,
+///     separator: false,
+///     section: false,
+///     description: None,
+///     tooltip: None,
+///     on_select: None,
+///     on_confirm: None,
+///     text_input: None,
+///     on_toggle: None,
+///     metadata: HashMap::new(),
+///     color: None,
+///     bg_color: None,
+///     label_refresh: None,
+///     radio: None,
+///     style: OptionStyle::default(),
+/// }
+/// # }
+/// ```
+impl<T> Default for MenuOption<T> {
+    fn default() -> MenuOption<T> {
+        MenuOption {
+            label: "exit".to_owned(),
+            value: None,
+            action: Box::new(|| {}),
+            checked: false,
+            disabled: false,
+            separator: false,
+            section: false,
+            description: None,
+            tooltip: None,
+            on_select: None,
+            on_confirm: None,
+            text_input: None,
+            on_toggle: None,
+            metadata: HashMap::new(),
+            color: None,
+            bg_color: None,
+            label_refresh: None,
+            radio: None,
+            style: OptionStyle::default(),
+        }
+    }
+}
+
+/// Shows the label and value of the option, and the keys of its metadata. Callbacks are left out.
+impl<T: std::fmt::Debug> std::fmt::Debug for MenuOption<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut metadata_keys: Vec<&String> = self.metadata.keys().collect();
+        metadata_keys.sort();
+        f.debug_struct("MenuOption")
+            .field("label", &self.label)
+            .field("value", &self.value)
+            .field("checked", &self.checked)
+            .field("disabled", &self.disabled)
+            .field("separator", &self.separator)
+            .field("section", &self.section)
+            .field("description", &self.description)
+            .field("tooltip", &self.tooltip)
+            .field("color", &self.color)
+            .field("bg_color", &self.bg_color)
+            .field("style", &self.style)
+            .field("metadata", &metadata_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A copy of a `MenuOption` without its value and callbacks, created with
+/// `MenuOption::clone_without_action`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MenuOptionTemplate {
+    pub label: String,
+    pub checked: bool,
+    pub disabled: bool,
+    pub separator: bool,
+    pub section: bool,
+    pub description: Option<String>,
+    pub tooltip: Option<String>,
+    pub color: Option<ColorMode>,
+    pub bg_color: Option<ColorMode>,
+    pub style: OptionStyle,
+    /// The keys of the option's metadata, sorted.
+    pub metadata_keys: Vec<String>,
+}
+
+/// Options are compared by label only, the action is ignored. Two options with the same label
+/// are equal even if their actions differ.
+impl<T> PartialEq for MenuOption<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+    }
+}
+
+impl<T> Eq for MenuOption<T> {}
+
+/// Options are ordered by label only, allowing option lists to be sorted alphabetically.
+impl<T> PartialOrd for MenuOption<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for MenuOption<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.label.cmp(&other.label)
+    }
+}
+
+/// A header displayed above a group of options.
+///
+/// Section headers are never selected, and are kept on the same page as the first option below
+/// them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MenuSection {
+    pub label: String,
+}
+
+impl<T> From<MenuSection> for MenuOption<T> {
+    fn from(section: MenuSection) -> Self {
+        MenuOption {
+            label: section.label,
+            section: true,
+            ..MenuOption::default()
+        }
+    }
+}
+
+/// An entry in a menu created with `Menu::with_entries`, either an option or a section header.
+///
+/// ```
+/// # use console_menu::{Key, Menu, MenuEntry, MenuOption, MenuProps};
+/// # use console_menu::testing::MockTerm;
+/// # let term = MockTerm::new(vec![Key::ArrowDown, Key::Enter]);
+/// let mut menu = Menu::with_entries(vec![
+///     MenuEntry::section("File"),
+///     MenuOption::new("open", || {}).into(),
+///     MenuEntry::section("Edit"),
+///     MenuOption::new("undo", || {}).into(),
+/// ], MenuProps::default());
+///
+/// // the cursor starts on "open" and skips the "Edit" header
+/// assert_eq!(menu.show_on_term(&term), Some(3));
+/// ```
+#[allow(clippy::large_enum_variant)] // entries only live until they're moved into a menu
+pub enum MenuEntry<T = ()> {
+    Option(MenuOption<T>),
+    Section(MenuSection),
+}
+
+impl<T> MenuEntry<T> {
+    /// Creates a section header entry.
+    pub fn section(label: &str) -> Self {
+        MenuEntry::Section(MenuSection { label: label.to_owned() })
+    }
+}
+
+impl<T> From<MenuOption<T>> for MenuEntry<T> {
+    fn from(option: MenuOption<T>) -> Self {
+        MenuEntry::Option(option)
+    }
+}
+
+impl<T> From<MenuSection> for MenuEntry<T> {
+    fn from(section: MenuSection) -> Self {
+        MenuEntry::Section(section)
+    }
+}
+
+/// The position of the cursor in a menu, saved with `Menu::snapshot` to be restored later.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MenuSnapshot {
+    /// The index of the selected option.
+    pub selected_option: usize,
+    /// The index of the selected page.
+    pub selected_page: usize,
+    /// The most options on a page when the snapshot was taken.
+    pub options_per_page: usize,
+    /// The number of pages when the snapshot was taken.
+    pub num_pages: usize,
+}
+
+type FrameHook<T> = Box<dyn Fn(&Menu<T>, u64)>;
+type OptionRenderer<T> = Box<dyn Fn(&MenuOption<T>, bool) -> String>;
+type OptionFilter<T> = Box<dyn Fn(&MenuOption<T>) -> bool>;
+
+/// The text being typed into a text input option.
+struct InputState {
+    index: usize,
+    text: String,
+}
+
+/// Interactive console menu.
+///
+/// Create a menu by passing it a list of `MenuOption` and a `MenuProps`. Display using`.show()`.
+///
+/// ```no_run
+/// # use console_menu::{Menu, MenuOption, MenuProps};
+/// let menu_options = vec![
+///     MenuOption::new("option 1", || println!("option one!")),
+///     MenuOption::new("option 2", || println!("option two!")),
+///     MenuOption::new("option 3", || println!("option three!")),
+/// ];
+/// let mut menu = Menu::new(menu_options, MenuProps::default());
+/// menu.show();
+/// ```
+///
+/// Menus can also be used to pick a value, with `.show()` returning the value of the selected
+/// option, or None if the menu was exited:
+///
+/// ```no_run
+/// # use console_menu::{Menu, MenuOption, MenuProps};
+/// let mut menu = Menu::new(vec![
+///     MenuOption::with_value("small", 8u32),
+///     MenuOption::with_value("medium", 16),
+///     MenuOption::with_value("large", 32),
+/// ], MenuProps::default());
+/// let size: Option<u32> = menu.show();
+/// ```
+pub struct Menu<T = ()> {
+    items: Vec<MenuOption<T>>,
+    title: Option<String>,
+    title_prefix: String,
+    title_suffix: String,
+    message: Option<String>,
+    exit_on_action: bool,
+    multi_run_mode: bool,
+    bg_color: ColorMode,
+    fg_color: ColorMode,
+    title_color: ColorMode,
+    selected_color: ColorMode,
+    msg_color: ColorMode,
+    disabled_color: Option<ColorMode>,
+    selected_item: usize,
+    selected_page: usize,
+    items_per_page: usize,
+    pages: Vec<Range<usize>>,
+    num_pages: usize,
+    page_start: usize,
+    page_end: usize,
+    max_width: usize,
+    column_width: usize,
+    frame_hook: Option<FrameHook<T>>,
+    option_renderer: Option<OptionRenderer<T>>,
+    page_change_callback: Option<Rc<dyn Fn(usize)>>,
+    key_repeat_delay: Duration,
+    key_repeat_rate: Duration,
+    multi_select: bool,
+    check_char: char,
+    uncheck_char: char,
+    separator_char: char,
+    page_size: Option<usize>,
+    key_bindings: KeyBindings,
+    no_color: bool,
+    max_label_width: Option<usize>,
+    color_support: Option<ColorSupport>,
+    border_style: BorderStyle,
+    border_color: ColorMode,
+    compact: bool,
+    columns: usize,
+    match_color: Option<ColorMode>,
+    show_shortcuts: bool,
+    remember_position: bool,
+    selection_prefix: Option<String>,
+    normal_prefix: Option<String>,
+    bold_selection: bool,
+    header_renderer: Option<Rc<dyn Fn() -> String>>,
+    footer_renderer: Option<Rc<dyn Fn() -> String>>,
+    show_scrollbar: bool,
+    layout: MenuLayout,
+    separator_str: String,
+    title_align: TitleAlign,
+    msg_align: MessageAlign,
+    menu_align: MenuAlign,
+    menu_valign: MenuVAlign,
+    breadcrumb: Option<String>,
+    show_key_hints: bool,
+    vertical_padding: MenuPadding,
+    horizontal_padding: MenuPadding,
+    show_loading: bool,
+    render_to_stderr: bool,
+    wrap_mode: WrapMode,
+    clear_on_show: bool,
+    clear_on_exit: bool,
+    screen_mode: ScreenMode,
+    pagination_format: Option<String>,
+    title_border: bool,
+    searching: bool,
+    search_query: String,
+    filter: Option<OptionFilter<T>>,
+    initial_selection: Option<usize>,
+    input: Option<InputState>,
+    pick_only: bool,
+    running_action: bool,
+    spinner_frame: usize,
+    history: Vec<MenuSnapshot>,
+    radio_groups: HashMap<String, usize>,
+    frame: u64,
+    term_size: (u16, u16),
+}
+
+impl<T> Menu<T> {
+    pub fn new(items: Vec<MenuOption<T>>, props: MenuProps) -> Self {
+        let mut items = items;
+        if items.is_empty() { items.push(MenuOption::default()) }
+
+        let mut menu = Self {
+            items,
+            title: if !props.title.is_empty() {
+                Some(props.title.to_owned())
+            } else {
+                None
+            },
+            title_prefix: props.title_prefix.to_owned(),
+            title_suffix: props.title_suffix.to_owned(),
+            message: if !props.message.is_empty() {
+                Some(props.message.to_owned())
+            } else {
+                None
+            },
+            exit_on_action: props.exit_on_action,
+            multi_run_mode: props.multi_run_mode,
+            bg_color: props.bg_color,
+            fg_color: props.fg_color,
+            title_color: props.title_color.unwrap_or(props.fg_color),
+            selected_color: props.selected_color.unwrap_or(props.fg_color),
+            msg_color: props.msg_color.unwrap_or(props.fg_color),
+            disabled_color: props.disabled_color,
+            selected_item: 0,
+            selected_page: 0,
+            items_per_page: 0,
+            pages: vec![],
+            num_pages: 0,
+            page_start: 0,
+            page_end: 0,
+            max_width: 0,
+            column_width: 0,
+            frame_hook: None,
+            option_renderer: None,
+            page_change_callback: props.page_change_callback,
+            key_repeat_delay: Duration::from_millis(props.key_repeat_delay_ms),
+            key_repeat_rate: Duration::from_millis(props.key_repeat_rate_ms),
+            multi_select: props.multi_select,
+            check_char: props.check_char,
+            uncheck_char: props.uncheck_char,
+            separator_char: props.separator_char,
+            page_size: props.page_size,
+            key_bindings: props.key_bindings,
+            no_color: props.no_color
+                || props.color_support == Some(ColorSupport::Monochrome)
+                || std::env::var_os("NO_COLOR").is_some_and(|val| !val.is_empty()),
+            max_label_width: props.max_label_width,
+            color_support: props.color_support,
+            border_style: props.border_style,
+            border_color: props.border_color.unwrap_or(props.fg_color),
+            compact: props.compact,
+            columns: props.columns.unwrap_or(1).max(1),
+            match_color: props.match_color,
+            show_shortcuts: props.show_shortcuts,
+            remember_position: props.remember_position,
+            selection_prefix: props.selection_prefix.map(str::to_owned),
+            normal_prefix: props.normal_prefix.map(str::to_owned),
+            bold_selection: props.bold_selection,
+            header_renderer: props.header_renderer,
+            footer_renderer: props.footer_renderer,
+            show_scrollbar: props.show_scrollbar,
+            layout: props.layout,
+            separator_str: props.separator_str.to_owned(),
+            title_align: props.title_align,
+            msg_align: props.msg_align,
+            menu_align: props.menu_align,
+            menu_valign: props.menu_valign,
+            breadcrumb: props.breadcrumb.map(|crumbs| crumbs.join(" > ")),
+            show_key_hints: props.show_key_hints,
+            vertical_padding: props.vertical_padding,
+            horizontal_padding: props.horizontal_padding,
+            show_loading: props.show_loading,
+            render_to_stderr: props.render_to_stderr,
+            #[allow(deprecated)]
+            wrap_mode: if props.wrap_selection && props.wrap_mode == WrapMode::NoWrap { WrapMode::WrapPages } else { props.wrap_mode },
+            clear_on_show: props.clear_on_show,
+            clear_on_exit: props.clear_on_exit,
+            #[allow(deprecated)]
+            screen_mode: if props.use_alternate_screen { ScreenMode::Alternate } else { props.screen_mode },
+            pagination_format: props.pagination_format.map(str::to_owned),
+            title_border: props.title_border,
+            searching: false,
+            search_query: String::new(),
+            filter: None,
+            initial_selection: None,
+            input: None,
+            pick_only: false,
+            running_action: false,
+            spinner_frame: 0,
+            history: Vec::new(),
+            radio_groups: HashMap::new(),
+            frame: 0,
+            term_size: Term::stdout().size(),
+        };
+        menu.update_radio_groups();
+        menu.update_layout();
+        menu.set_page(0);
+        menu
+    }
+
+    /// Creates a menu from an iterator of options. Like `Menu::new`, an empty iterator results in
+    /// a menu with only the default exit option.
+    pub fn from_iter_with_props(options: impl IntoIterator<Item = MenuOption<T>>, props: MenuProps) -> Self {
+        Menu::new(options.into_iter().collect(), props)
+    }
+
+    /// Creates a menu from a list of options and section headers. Sections are stored as options
+    /// with `MenuOption::section` set.
+    pub fn with_entries(entries: Vec<MenuEntry<T>>, props: MenuProps) -> Self {
+        let items = entries.into_iter().map(|entry| match entry {
+            MenuEntry::Option(option) => option,
+            MenuEntry::Section(section) => section.into(),
+        }).collect();
+        Menu::new(items, props)
+    }
+
+    /// Creates a new menu with the same title, message, colors, behavior and width as this one,
+    /// but containing only a default exit option. Callbacks and hooks are not carried over.
+    ///
+    /// Useful for creating sibling menus with identical styling.
+    pub fn clone_layout(&self) -> Menu<T> {
+        let mut menu = Menu::new(vec![], MenuProps {
+            title: self.title.as_deref().unwrap_or(""),
+            title_prefix: &self.title_prefix,
+            title_suffix: &self.title_suffix,
+            message: self.message.as_deref().unwrap_or(""),
+            exit_on_action: self.exit_on_action,
+            multi_run_mode: self.multi_run_mode,
+            bg_color: self.bg_color,
+            fg_color: self.fg_color,
+            title_color: Some(self.title_color),
+            selected_color: Some(self.selected_color),
+            msg_color: Some(self.msg_color),
+            disabled_color: self.disabled_color,
+            key_repeat_delay_ms: self.key_repeat_delay.as_millis() as u64,
+            key_repeat_rate_ms: self.key_repeat_rate.as_millis() as u64,
+            multi_select: self.multi_select,
+            check_char: self.check_char,
+            uncheck_char: self.uncheck_char,
+            separator_char: self.separator_char,
+            page_size: self.page_size,
+            key_bindings: self.key_bindings.clone(),
+            no_color: self.no_color,
+            max_label_width: self.max_label_width,
+            color_support: self.color_support,
+            border_style: self.border_style,
+            border_color: Some(self.border_color),
+            compact: self.compact,
+            columns: Some(self.columns),
+            match_color: self.match_color,
+            show_shortcuts: self.show_shortcuts,
+            remember_position: self.remember_position,
+            selection_prefix: self.selection_prefix.as_deref(),
+            normal_prefix: self.normal_prefix.as_deref(),
+            bold_selection: self.bold_selection,
+            show_scrollbar: self.show_scrollbar,
+            layout: self.layout,
+            separator_str: &self.separator_str,
+            title_align: self.title_align,
+            msg_align: self.msg_align,
+            menu_align: self.menu_align,
+            menu_valign: self.menu_valign,
+            breadcrumb: self.breadcrumb.as_ref().map(|breadcrumb| vec![breadcrumb.clone()]),
+            show_key_hints: self.show_key_hints,
+            vertical_padding: self.vertical_padding,
+            horizontal_padding: self.horizontal_padding,
+            show_loading: self.show_loading,
+            render_to_stderr: self.render_to_stderr,
+            wrap_mode: self.wrap_mode,
+            clear_on_show: self.clear_on_show,
+            clear_on_exit: self.clear_on_exit,
+            screen_mode: self.screen_mode,
+            pagination_format: self.pagination_format.as_deref(),
+            title_border: self.title_border,
+            ..MenuProps::default()
+        });
+        menu.max_width = self.max_width;
+        menu
+    }
+
+    /// Registers a hook called before every redraw of the menu.
+    ///
+    /// The hook receives the menu and a frame counter starting at 0, which allows external UI
+    /// elements to synchronize their own redraws with the menu.
+    ///
+    /// ```
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let menu = Menu::new(vec![MenuOption::new("option", || {})], MenuProps::default())
+    ///     .with_frame_hook(|_menu, frame| eprintln!("drawing frame {}", frame));
+    /// ```
+    pub fn with_frame_hook(mut self, f: impl Fn(&Menu<T>, u64) + 'static) -> Self {
+        self.frame_hook = Some(Box::new(f));
+        self
+    }
+
+    /// Registers a function drawing options in place of the default appearance.
+    ///
+    /// The function receives each option and whether it is selected, and returns the text of its
+    /// row, including any colors or styles. Only the menu background is added. The menu is made
+    /// wide enough for the returned text, but the text should have the same width whether or not
+    /// the option is selected to keep the menu aligned. Separators, section headers and
+    /// descriptions are still drawn by the menu.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// # let term = MockTerm::new(vec![Key::Escape]);
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("first", || {}),
+    ///     MenuOption::new("second", || {}),
+    /// ], MenuProps::default())
+    ///     .with_option_renderer(|option, selected| {
+    ///         format!("{}{}", option.label, if selected { " [SELECTED]" } else { "" })
+    ///     });
+    /// menu.show_on_term(&term);
+    ///
+    /// let output = term.output();
+    /// assert!(output.contains("first [SELECTED]"));
+    /// assert!(!output.contains("second [SELECTED]"));
+    /// ```
+    pub fn with_option_renderer(mut self, f: impl Fn(&MenuOption<T>, bool) -> String + 'static) -> Self {
+        self.option_renderer = Some(Box::new(f));
+        self.update_layout();
+        self
+    }
+
+    /// Draws a border of the given style and color around the menu. The options are laid out
+    /// again, as the border takes up a column on each side and a row above and below the menu.
+    ///
+    /// ```
+    /// # use console_menu::{color, BorderStyle, Menu};
+    /// let mut menu = Menu::from_labels(["one", "two"]).with_border(BorderStyle::Double, color::CYAN);
+    /// assert!(menu.render_to_string(40, 12).contains('╔'));
+    ///
+    /// let mut menu = menu.without_border();
+    /// assert!(!menu.render_to_string(40, 12).contains('╔'));
+    /// ```
+    pub fn with_border(mut self, style: BorderStyle, color: impl Into<ColorMode>) -> Self {
+        self.border_style = style;
+        self.border_color = color.into();
+        self.refresh_options();
+        self
+    }
+
+    /// Removes the border around the menu, laying out the options again.
+    pub fn without_border(mut self) -> Self {
+        self.border_style = BorderStyle::None;
+        self.refresh_options();
+        self
+    }
+
+    /// Displays the menu and runs the action of the selected option, returning the value of the
+    /// option that exited it, or None if the user exited. Options without a value, like the
+    /// default exit option, also return None.
+    ///
+    /// When `exit_on_action` is false, selecting an option doesn't exit the menu and None is
+    /// returned once the user exits. To pick an option without running its action, use
+    /// `run_once` instead.
+    pub fn show(&mut self) -> Option<T> where T: Clone {
+        let index = self.show_with_result()?;
+        self.items[index].value.clone()
+    }
+
+    /// Displays the menu as a picker, returning the index of the option selected with enter, or
+    /// None if the user exited. Unlike `show()`, the option's action isn't run and the menu always
+    /// exits once an option is selected, leaving the caller to decide what happens next.
+    ///
+    /// ```no_run
+    /// # use console_menu::Menu;
+    /// let files = ["a.txt", "b.txt", "c.txt"];
+    /// if let Some(index) = Menu::from_labels(files).run_once() {
+    ///     println!("opening {}", files[index]);
+    /// }
+    /// ```
+    pub fn run_once(&mut self) -> Option<usize> {
+        self.run_once_on_term(self.default_term())
+    }
+
+    /// Displays the menu as a picker on the given terminal, like `run_once`.
+    ///
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::rc::Rc;
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let ran = Rc::new(Cell::new(false));
+    /// let action_ran = Rc::clone(&ran);
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("keep", || {}),
+    ///     MenuOption::new("delete", move || action_ran.set(true)),
+    /// ], MenuProps::default());
+    ///
+    /// let term = MockTerm::new(vec![Key::ArrowDown, Key::Enter]);
+    /// assert_eq!(menu.run_once_on_term(&term), Some(1));
+    /// assert!(!ran.get());
+    /// ```
+    pub fn run_once_on_term(&mut self, term: impl TerminalOutput) -> Option<usize> {
+        self.pick_only = true;
+        let result = self.try_show_on_term(term);
+        self.pick_only = false;
+        result.expect("failed to show menu")
+    }
+
+    /// Displays the menu with the option at `initial_index` selected, returning the value of the
+    /// option that exited it like `show()`.
+    ///
+    /// ```no_run
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let themes = ["light", "dark", "system"];
+    /// let mut menu = Menu::new(
+    ///     themes.iter().map(|theme| MenuOption::with_value(theme, *theme)).collect(),
+    ///     MenuProps { title: "Theme", ..MenuProps::default() },
+    /// );
+    /// let theme = menu.show_at(1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_index` is out of bounds.
+    pub fn show_at(&mut self, initial_index: usize) -> Option<T> where T: Clone {
+        self.set_initial_selection(initial_index);
+        self.show()
+    }
+
+    /// Selects the option at `index` the next time the menu is shown. If that option can't be
+    /// selected, the closest selectable option after it is used instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_initial_selection(&mut self, index: usize) {
+        assert!(
+            index < self.items.len(),
+            "initial selection {} is out of bounds for a menu with {} options",
+            index,
+            self.items.len(),
+        );
+        self.initial_selection = Some(index);
+    }
+
+    /// Displays the menu, returning the index of the option that exited it, or None if the user
+    /// exited.
+    ///
+    /// ```no_run
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("start", || {}),
+    ///     MenuOption::new("stop", || {}),
+    /// ], MenuProps::default());
+    /// match menu.show_with_result() {
+    ///     Some(0) => println!("starting"),
+    ///     Some(_) => println!("stopping"),
+    ///     None => println!("cancelled"),
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the menu can't be displayed, see `try_show`.
+    pub fn show_with_result(&mut self) -> Option<usize> {
+        self.try_show().expect("failed to show menu")
+    }
+
+    /// Displays the menu like `show_with_result`, returning an error instead of panicking if the
+    /// terminal can't be written to or read from, as is the case when stdout isn't a terminal.
+    ///
+    /// ```no_run
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let mut menu = Menu::new(vec![MenuOption::new("start", || {})], MenuProps::default());
+    /// match menu.try_show() {
+    ///     Ok(Some(_)) => println!("starting"),
+    ///     Ok(None) => println!("cancelled"),
+    ///     Err(err) => eprintln!("couldn't show menu: {}", err),
+    /// }
+    /// ```
+    pub fn try_show(&mut self) -> Result<Option<usize>, MenuError> {
+        self.try_show_on_term(self.default_term())
+    }
+
+    /// Displays the menu like `show()`, then prints the label of the selected option followed by a
+    /// newline to stdout. Nothing is printed if the user exited. When stdout isn't a terminal, the
+    /// menu is displayed on stderr so the label can be captured by a shell:
+    ///
+    /// ```sh
+    /// my_tool --pick 2>/dev/tty | xargs process_selection
+    /// ```
+    pub fn show_and_print_selection(&mut self) -> Option<T> where T: Clone {
+        let term = if Term::stdout().is_term() { self.default_term() } else { Term::buffered_stderr() };
+        let index = self.show_on_term_and_print_selection(term, &mut io::stdout())?;
+        self.items[index].value.clone()
+    }
+
+    /// Displays the menu on the given terminal, then prints the label of the selected option to
+    /// `out` like `show_and_print_selection`. Returns the index of the selected option, or None if
+    /// the user exited.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let mut menu = Menu::from_labels(["main", "develop", "release"]);
+    /// let term = MockTerm::new(vec![Key::ArrowDown, Key::Enter]);
+    /// let mut stdout = Vec::new();
+    /// menu.show_on_term_and_print_selection(&term, &mut stdout);
+    /// assert_eq!(stdout, b"develop\n");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the menu can't be displayed or writing to `out` fails.
+    pub fn show_on_term_and_print_selection(&mut self, term: impl TerminalOutput, out: &mut impl io::Write) -> Option<usize> {
+        let index = self.show_on_term(term)?;
+        writeln!(out, "{}", self.items[index].label).expect("failed to print selection");
+        Some(index)
+    }
+
+    /// Displays the menu on stderr instead of stdout, keeping stdout clean for piped output.
+    ///
+    /// Returns the index of the option that exited the menu, or None if the user exited.
+    pub fn show_on_stderr(&mut self) -> Option<usize> {
+        self.show_on_term(Term::buffered_stderr())
+    }
+
+    /// Displays the menu on the given terminal.
+    ///
+    /// Returns the index of the option that exited the menu, or None if the user exited.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the menu can't be displayed, see `try_show_on_term`.
+    pub fn show_on_term(&mut self, term: impl TerminalOutput) -> Option<usize> {
+        self.try_show_on_term(term).expect("failed to show menu")
+    }
+
+    /// Displays the menu on the given terminal, returning an error if writing to it or reading a
+    /// key from it fails.
+    ///
+    /// ```
+    /// # use std::cell::Cell;
+    /// # use std::io;
+    /// # use console_menu::{Key, Menu, MenuError, MenuOption, MenuProps, TerminalOutput};
+    /// struct FailingTerm {
+    ///     writes: Cell<usize>,
+    /// }
+    ///
+    /// impl TerminalOutput for FailingTerm {
+    ///     fn write_str(&self, _: &str) -> io::Result<()> {
+    ///         self.writes.set(self.writes.get() + 1);
+    ///         if self.writes.get() == 3 {
+    ///             return Err(io::Error::new(io::ErrorKind::BrokenPipe, "terminal closed"));
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// #
+    /// #     fn size(&self) -> (u16, u16) {
+    /// #         (24, 80)
+    /// #     }
+    /// #
+    /// #     fn read_key(&self) -> io::Result<Key> {
+    /// #         Ok(Key::Escape)
+    /// #     }
+    /// }
+    ///
+    /// let mut menu = Menu::new(vec![MenuOption::new("start", || {})], MenuProps::default());
+    /// let result = menu.try_show_on_term(FailingTerm { writes: Cell::new(0) });
+    /// assert!(matches!(result, Err(MenuError::TerminalError(_))));
+    /// ```
+    ///
+    /// Also returns `MenuError::InvalidIndex` if the initial selection was removed before showing
+    /// the menu.
+    pub fn try_show_on_term(&mut self, term: impl TerminalOutput) -> Result<Option<usize>, MenuError> {
+        self.searching = false;
+        self.search_query.clear();
+        self.input = None;
+        if let Some(index) = self.initial_selection.filter(|&index| index >= self.items.len()) {
+            self.initial_selection = None;
+            return Err(MenuError::InvalidIndex(index));
+        }
+
+        term.write_str(self.screen_mode.enter_sequence())?;
+        term.hide_cursor()?;
+        // lay out from scratch, as options may have changed through `option_mut`
+        self.term_size = term.size();
+        self.refresh_options();
+
+        if self.clear_on_show && self.screen_mode == ScreenMode::Main {
+            let term_height = term.size().0 as usize;
+            term.write_str(&"\n".repeat(term_height.saturating_sub(1)))?;
+        }
+
+        if let Some(index) = self.initial_selection.take() {
+            self.select_item(self.closest_selectable(index));
+        } else if !self.remember_position {
+            self.set_page(0);
+        }
+        self.redraw(&term)?;
+        self.run_navigation(&term)
+    }
+
+    /// Displays the menu in multi-select mode, returning the indices of all checked options once
+    /// the user confirms with enter. Exiting the menu returns an empty list.
+    ///
+    /// Equivalent to `show()` with `MenuProps::multi_select` set.
+    ///
+    /// ```no_run
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let files = ["a.txt", "b.txt", "c.txt"];
+    /// let mut menu = Menu::new(
+    ///     files.iter().map(|file| MenuOption::with_value(file, *file)).collect(),
+    ///     MenuProps::default(),
+    /// );
+    /// for i in menu.show_multi() {
+    ///     println!("processing {}", files[i]);
+    /// }
+    /// ```
+    pub fn show_multi(&mut self) -> Vec<usize> {
+        let multi_select = self.multi_select;
+        self.multi_select = true;
+        self.update_layout();
+        let confirmed = self.show_on_term(self.default_term()).is_some();
+        self.multi_select = multi_select;
+        self.update_layout();
+
+        if !confirmed {
+            return vec![];
+        }
+        self.items.iter().enumerate()
+            .filter(|(_, item)| item.checked)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Displays the menu in multi-run mode, leaving it open after each selected action so that
+    /// several options can be run in sequence. The menu exits on esc.
+    ///
+    /// Equivalent to `show()` with `MenuProps::multi_run_mode` set.
+    pub fn show_and_run_all(&mut self) {
+        let multi_run_mode = self.multi_run_mode;
+        self.multi_run_mode = true;
+        self.show_on_term(self.default_term());
+        self.multi_run_mode = multi_run_mode;
+    }
+
+    /// Sets the background color, taking effect on the next redraw.
+    pub fn set_bg_color(&mut self, color: impl Into<ColorMode>) {
+        self.bg_color = color.into();
+    }
+
+    /// Sets the foreground (text) color, taking effect on the next redraw.
+    pub fn set_fg_color(&mut self, color: impl Into<ColorMode>) {
+        self.fg_color = color.into();
+    }
+
+    /// Sets the color of the selected menu option, taking effect on the next redraw.
+    pub fn set_selected_color(&mut self, color: impl Into<ColorMode>) {
+        self.selected_color = color.into();
+    }
+
+    /// Sets the title color, taking effect on the next redraw.
+    pub fn set_title_color(&mut self, color: impl Into<ColorMode>) {
+        self.title_color = color.into();
+    }
+
+    /// Sets the footer message color, taking effect on the next redraw.
+    pub fn set_msg_color(&mut self, color: impl Into<ColorMode>) {
+        self.msg_color = color.into();
+    }
+
+    /// Switches the menu to the colors of `theme`, taking effect on the next redraw. Unlike
+    /// `with_props`, the layout, text and behavior of the menu are left as they are. Colors the
+    /// theme leaves unset follow its foreground color, like in `MenuProps::with_theme`.
+    ///
+    /// ```
+    /// # use console_menu::{theme, Menu};
+    /// # use console_menu::testing::MockTerm;
+    /// let mut menu = Menu::from_labels(["light", "dark"]);
+    /// menu.with_title("Appearance");
+    /// menu.apply_theme(theme::MONOKAI);
+    ///
+    /// let term = MockTerm::new(vec![]);
+    /// menu.draw_on_term(&term).unwrap();
+    /// let output = term.output();
+    /// assert!(output.contains("\x1b[48;5;235m") && output.contains("\x1b[38;5;197m"));
+    /// assert!(output.contains("Appearance"));
+    /// ```
+    pub fn apply_theme(&mut self, theme: theme::Theme) {
+        self.bg_color = theme.bg_color;
+        self.fg_color = theme.fg_color;
+        self.title_color = theme.title_color.unwrap_or(theme.fg_color);
+        self.selected_color = theme.selected_color.unwrap_or(theme.fg_color);
+        self.msg_color = theme.msg_color.unwrap_or(theme.fg_color);
+    }
+
+    /// Applies new properties to the menu, keeping its options, selected option, frame hook,
+    /// option renderer and filter. The pages are recomputed for the new properties, so the selected page is
+    /// the one holding the selected option.
+    ///
+    /// ```
+    /// # use console_menu::{color, Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("light", || {}),
+    ///     MenuOption::new("dark", || {}),
+    /// ], MenuProps { bg_color: color::BLUE.into(), ..MenuProps::default() });
+    /// menu.handle_key(Key::ArrowDown);
+    ///
+    /// menu.with_props(MenuProps { title: "Themes", bg_color: color::RED.into(), ..MenuProps::default() });
+    /// let term = MockTerm::new(vec![]);
+    /// menu.draw_on_term(&term).unwrap();
+    ///
+    /// let output = term.output();
+    /// assert!(output.contains("\x1b[48;5;160m") && !output.contains("\x1b[48;5;32m"));
+    /// assert!(output.contains("Themes"));
+    /// assert_eq!(menu.snapshot().selected_option, 1);
+    /// ```
+    pub fn with_props(&mut self, props: MenuProps) {
+        let items = std::mem::take(&mut self.items);
+        let old = std::mem::replace(self, Menu::new(items, props));
+        self.frame_hook = old.frame_hook;
+        self.option_renderer = old.option_renderer;
+        self.filter = old.filter;
+        self.initial_selection = old.initial_selection;
+        self.history = old.history;
+        self.frame = old.frame;
+        self.term_size = old.term_size;
+        self.refresh_options();
+        let selected = self.closest_selectable(old.selected_item.min(self.items.len() - 1));
+        self.select_item(selected);
+    }
+
+    /// Sets the title, or removes it if `title` is empty.
+    pub fn with_title(&mut self, title: &str) {
+        self.title = Some(title.to_owned()).filter(|title| !title.is_empty());
+        self.refresh_options();
+    }
+
+    /// Sets the footer message, or removes it if `message` is empty.
+    pub fn with_message(&mut self, message: &str) {
+        self.message = Some(message.to_owned()).filter(|message| !message.is_empty());
+        self.refresh_options();
+    }
+
+    /// Removes consecutive options with duplicate labels, keeping the first of each run. Sort the
+    /// options beforehand to remove all duplicates. Resets the selection to the first option.
+    pub fn dedup_options(&mut self) {
+        self.items.dedup();
+        self.update_layout();
+        self.set_page(0);
+    }
+
+    /// Sorts the options with the comparison function `cmp`. Separators and section headers stay
+    /// in place, and the options between them are sorted among themselves. Resets the selection
+    /// to the first option.
+    ///
+    /// ```
+    /// # use console_menu::{Menu, MenuOption, MenuProps, MenuSection};
+    /// let mut menu = Menu::new(vec![
+    ///     MenuSection { label: "Fruit".to_owned() }.into(),
+    ///     MenuOption::with_value("Mango", 3),
+    ///     MenuOption::with_value("Apple", 12),
+    ///     MenuSection { label: "Vegetables".to_owned() }.into(),
+    ///     MenuOption::with_value("Leek", 1),
+    ///     MenuOption::with_value("Carrot", 8),
+    /// ], MenuProps::default());
+    ///
+    /// menu.sort_options_by(|a, b| b.value.cmp(&a.value));
+    /// let labels: Vec<_> = (0..6).map(|i| menu.option(i).unwrap().label.as_str()).collect();
+    /// assert_eq!(labels, ["Fruit", "Apple", "Mango", "Vegetables", "Carrot", "Leek"]);
+    /// ```
+    pub fn sort_options_by(&mut self, mut cmp: impl FnMut(&MenuOption<T>, &MenuOption<T>) -> std::cmp::Ordering) {
+        for group in self.option_groups() {
+            self.items[group].sort_by(&mut cmp);
+        }
+        self.reorder_options();
+    }
+
+    /// Sorts the options by label, see `sort_options_by`.
+    ///
+    /// ```
+    /// # use console_menu::Menu;
+    /// let mut menu = Menu::from_labels(["Zebra", "Apple", "Mango"]);
+    /// menu.sort_options_alphabetically();
+    /// assert_eq!(menu.option(0).unwrap().label, "Apple");
+    /// assert_eq!(menu.option(2).unwrap().label, "Zebra");
+    /// ```
+    pub fn sort_options_alphabetically(&mut self) {
+        self.sort_options_by(|a, b| a.label.cmp(&b.label));
+    }
+
+    /// Reverses the order of the options. Like `sort_options_by`, separators and section headers
+    /// stay in place and the options between them are reversed. Resets the selection to the
+    /// first option.
+    pub fn reverse_options(&mut self) {
+        for group in self.option_groups() {
+            self.items[group].reverse();
+        }
+        self.reorder_options();
+    }
+
+    /// Adds an option to the end of the menu. The menu is resized and paginated again to fit it.
+    ///
+    /// ```
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let options = vec![MenuOption::with_value("a", 1), MenuOption::with_value("b", 2)];
+    /// let mut menu = Menu::new(options, MenuProps { page_size: Some(2), ..MenuProps::default() });
+    /// assert_eq!(menu.items_on_page(1), 0);
+    ///
+    /// menu.add_option(MenuOption::with_value("c", 3));
+    /// assert_eq!(menu.items_on_page(1), 1);
+    /// ```
+    pub fn add_option(&mut self, option: MenuOption<T>) {
+        self.items.push(option);
+        self.refresh_options();
+    }
+
+    /// Adds several options to the end of the menu.
+    pub fn add_options(&mut self, options: Vec<MenuOption<T>>) {
+        self.items.extend(options);
+        self.refresh_options();
+    }
+
+    /// Removes and returns the option at `index`, or None if there is no such option. If the
+    /// selected option is removed, the option before it is selected.
+    ///
+    /// Removing the last option leaves the default exit option in its place, like creating a menu
+    /// with no options.
+    pub fn remove_option(&mut self, index: usize) -> Option<MenuOption<T>> {
+        if index >= self.items.len() {
+            return None;
+        }
+        let option = self.items.remove(index);
+        if index < self.selected_item || (index == self.selected_item && index > 0) {
+            self.selected_item -= 1;
+        }
+        self.refresh_options();
+        Some(option)
+    }
+
+    /// Removes every option, leaving only the default exit option like creating a menu with no
+    /// options. Showing the menu afterwards displays the exit option, which returns None.
+    pub fn clear_options(&mut self) {
+        self.items.clear();
+        self.refresh_options();
+    }
+
+    /// Returns the option at `index`, or None if there is no such option.
+    pub fn option(&self, index: usize) -> Option<&MenuOption<T>> {
+        self.items.get(index)
+    }
+
+    /// Returns the option at `index` to be changed, or None if there is no such option. Changes
+    /// to the option are laid out the next time the menu is shown.
+    pub fn option_mut(&mut self, index: usize) -> Option<&mut MenuOption<T>> {
+        self.items.get_mut(index)
+    }
+
+    /// Returns the option at `index`, or None if there is no such option. Same as `option`.
+    ///
+    /// ```
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::with_value("red", 1),
+    ///     MenuOption::with_value("green", 2),
+    /// ], MenuProps::default());
+    ///
+    /// let index = menu.find_option(|option| option.label.starts_with('g')).unwrap();
+    /// assert_eq!(menu.get_option(index).unwrap().value, Some(2));
+    /// menu.get_option_mut(index).unwrap().label = "lime".to_owned();
+    /// assert_eq!(menu.option(1).unwrap().label, "lime");
+    /// assert!(menu.get_option(2).is_none());
+    /// ```
+    pub fn get_option(&self, index: usize) -> Option<&MenuOption<T>> {
+        self.option(index)
+    }
+
+    /// Returns the option at `index` to be changed, or None if there is no such option. Same as
+    /// `option_mut`.
+    pub fn get_option_mut(&mut self, index: usize) -> Option<&mut MenuOption<T>> {
+        self.option_mut(index)
+    }
+
+    /// Disables the option at `index`, so it's displayed but skipped during navigation. If it was
+    /// selected, the closest enabled option before it is selected instead.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::with_value("new game", 1),
+    ///     MenuOption::with_value("continue", 2),
+    ///     MenuOption::with_value("quit", 3),
+    /// ], MenuProps::default());
+    /// menu.disable_option(1);
+    ///
+    /// // moving down skips "continue"
+    /// assert_eq!(menu.show_on_term(MockTerm::new(vec![Key::ArrowDown, Key::Enter])), Some(2));
+    ///
+    /// menu.toggle_option_enabled(1);
+    /// assert!(!menu.option(1).unwrap().disabled);
+    ///
+    /// // with nothing enabled the menu can still be shown, and exited
+    /// menu.disable_all();
+    /// assert_eq!(menu.snapshot().selected_option, 0);
+    /// assert_eq!(menu.show_on_term(MockTerm::new(vec![Key::ArrowDown, Key::Enter, Key::Escape])), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn disable_option(&mut self, index: usize) {
+        self.items[index].set_disabled(true);
+        self.refresh_options();
+    }
+
+    /// Enables the option at `index`, so it can be selected again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn enable_option(&mut self, index: usize) {
+        self.items[index].set_disabled(false);
+        self.refresh_options();
+    }
+
+    /// Disables the option at `index` if it's enabled, or enables it if it's disabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn toggle_option_enabled(&mut self, index: usize) {
+        let disabled = self.items[index].disabled;
+        self.items[index].set_disabled(!disabled);
+        self.refresh_options();
+    }
+
+    /// Disables every option. The menu can still be shown and exited, with the first option
+    /// selected until options are enabled again.
+    pub fn disable_all(&mut self) {
+        for item in &mut self.items {
+            item.set_disabled(true);
+        }
+        self.selected_item = 0;
+        self.refresh_options();
+    }
+
+    /// Enables every option.
+    pub fn enable_all(&mut self) {
+        for item in &mut self.items {
+            item.set_disabled(false);
+        }
+        self.refresh_options();
+    }
+
+    /// Returns the index of the first option matching `predicate`, or None if no option matches.
+    ///
+    /// ```
+    /// # use console_menu::Menu;
+    /// let menu = Menu::from_labels(["src/main.rs", "src/lib.rs", "README.md", "tests/menu.rs", "src/bin/cli.rs"]);
+    ///
+    /// assert_eq!(menu.find_option(|option| option.label.starts_with("tests/")), Some(3));
+    /// assert_eq!(menu.find_option(|option| option.label.ends_with(".toml")), None);
+    /// assert_eq!(menu.find_all_options(|option| option.label.starts_with("src/")), [0, 1, 4]);
+    /// ```
+    pub fn find_option(&self, predicate: impl Fn(&MenuOption<T>) -> bool) -> Option<usize> {
+        self.items.iter().position(predicate)
+    }
+
+    /// Returns the indices of all options matching `predicate`, in order.
+    pub fn find_all_options(&self, predicate: impl Fn(&MenuOption<T>) -> bool) -> Vec<usize> {
+        self.items.iter().enumerate().filter(|(_, option)| predicate(option)).map(|(i, _)| i).collect()
+    }
+
+    /// Hides every option `filter` returns false for, replacing any filter applied before. The
+    /// options left are listed one per row, and the selection moves to one of them if it was
+    /// hidden.
+    ///
+    /// The filter takes precedence over the search query: applying it ends a search, and the
+    /// search key does nothing until the filter is cleared.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::with_value("apple", 3),
+    ///     MenuOption::with_value("melon", 12),
+    ///     MenuOption::with_value("plum", 2),
+    ///     MenuOption::with_value("pumpkin", 40),
+    /// ], MenuProps { no_color: true, ..MenuProps::default() });
+    ///
+    /// menu.apply_filter_fn(|option| option.value.is_some_and(|price| price < 10));
+    /// assert_eq!((menu.visible_option_count(), menu.hidden_option_count()), (2, 2));
+    /// let output = menu.render_to_string(40, 12);
+    /// assert!(output.contains("apple") && output.contains("plum") && !output.contains("melon"));
+    ///
+    /// // navigation skips the hidden options
+    /// menu.handle_key(Key::ArrowDown);
+    /// assert_eq!(menu.snapshot().selected_option, 2);
+    ///
+    /// menu.clear_filter();
+    /// assert_eq!(menu.visible_option_count(), 4);
+    /// assert!(menu.render_to_string(40, 12).contains("melon"));
+    /// ```
+    pub fn apply_filter_fn(&mut self, filter: impl Fn(&MenuOption<T>) -> bool + 'static) {
+        self.filter = Some(Box::new(filter));
+        self.searching = false;
+        self.search_query.clear();
+        self.refresh_options();
+    }
+
+    /// Removes the filter applied with `apply_filter_fn` and ends any search, showing every
+    /// option again.
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+        self.searching = false;
+        self.search_query.clear();
+        self.refresh_options();
+    }
+
+    /// Returns the number of options shown by the filter applied with `apply_filter_fn`, or
+    /// matching the search query while searching. Disabled options, separators and section
+    /// headers count as visible unless they're filtered out. Without a filter or search, this is
+    /// the number of options in the menu.
+    pub fn visible_option_count(&self) -> usize {
+        (0..self.items.len()).filter(|&i| self.is_visible(i)).count()
+    }
+
+    /// Returns the number of options hidden by the filter or search query, the options not
+    /// counted by `visible_option_count`.
+    pub fn hidden_option_count(&self) -> usize {
+        self.items.len() - self.visible_option_count()
+    }
+
+    /// Returns the options displayed on the given page, or None if the page doesn't exist.
+    pub fn page_options(&self, page: usize) -> Option<&[MenuOption<T>]> {
+        let range = self.pages.get(page)?;
+        Some(&self.items[range.clone()])
+    }
+
+    /// Returns the maximum number of options displayed on a page.
+    ///
+    /// ```
+    /// # use console_menu::{Menu, MenuOption, MenuProps};
+    /// let options = (1..=7).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+    /// let menu = Menu::new(options, MenuProps { page_size: Some(3), ..MenuProps::default() });
+    /// assert_eq!(menu.options_per_page(), 3);
+    /// assert_eq!(menu.items_on_page(1), 3);
+    /// assert_eq!(menu.items_on_page(2), 1);
+    /// ```
+    ///
+    /// Without a `page_size`, pages fill the height of the terminal. If the terminal is resized
+    /// while the menu is shown, the pages are recomputed and the menu redrawn straight away on
+    /// terminals that provide `TerminalOutput::clone_for_thread`, and on the next key press on
+    /// others:
+    ///
+    /// ```
+    /// # use std::thread;
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let term = MockTerm::new(vec![]).waiting_for_keys();
+    /// let options = (1..=30).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+    /// let mut menu = Menu::new(options, MenuProps::default());
+    ///
+    /// thread::scope(|scope| {
+    ///     scope.spawn(|| {
+    ///         // wait for the first frame, shrink the terminal, then wait for the redraw
+    ///         while term.frames().is_empty() {
+    ///             thread::yield_now();
+    ///         }
+    ///         term.resize(8, 80);
+    ///         while term.frames().len() < 2 {
+    ///             thread::yield_now();
+    ///         }
+    ///         term.send_key(Key::Escape);
+    ///     });
+    ///     menu.show_on_term(&term);
+    /// });
+    /// assert_eq!(menu.options_per_page(), 5);
+    /// assert!(term.frames()[1].contains("Page 1 of 6"));
+    /// ```
+    pub fn options_per_page(&self) -> usize {
+        self.items_per_page
+    }
+
+    /// Returns the number of options displayed on the currently selected page, which may be less
+    /// than the page size on the last page.
+    pub fn items_on_current_page(&self) -> usize {
+        self.page_end - self.page_start + 1
+    }
+
+    /// Returns the number of options displayed on the given page, or 0 if the page doesn't exist.
+    pub fn items_on_page(&self, page: usize) -> usize {
+        self.page_options(page).map_or(0, |options| options.len())
+    }
+
+    /// Returns the options displayed on the currently selected page.
+    pub fn current_page_options(&self) -> &[MenuOption<T>] {
+        &self.items[self.page_start..=self.page_end]
+    }
+
+    /// Saves the selected option and page, to return to them with `restore_snapshot`.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// let options = (1..=30).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+    /// let mut menu = Menu::new(options, MenuProps { page_size: Some(3), ..MenuProps::default() });
+    ///
+    /// menu.handle_key(Key::ArrowRight);
+    /// let snapshot = menu.snapshot();
+    /// assert_eq!(snapshot.selected_page, 1);
+    ///
+    /// for _ in 0..3 {
+    ///     menu.handle_key(Key::ArrowRight);
+    /// }
+    /// assert_eq!(menu.snapshot().selected_page, 4);
+    ///
+    /// menu.restore_snapshot(snapshot);
+    /// assert_eq!(menu.snapshot().selected_page, 1);
+    /// assert_eq!(menu.snapshot().selected_option, 3);
+    /// ```
+    pub fn snapshot(&self) -> MenuSnapshot {
+        MenuSnapshot {
+            selected_option: self.selected_item,
+            selected_page: self.selected_page,
+            options_per_page: self.items_per_page,
+            num_pages: self.num_pages,
+        }
+    }
+
+    /// Selects the option saved in `snapshot`, and shows the menu there the next time it's
+    /// shown. The page is worked out from the option, as the pages may have changed since. If
+    /// options were removed since, the last option is selected instead.
+    pub fn restore_snapshot(&mut self, snapshot: MenuSnapshot) {
+        if self.items.is_empty() {
+            return;
+        }
+        let selected = self.closest_selectable(snapshot.selected_option.min(self.items.len() - 1));
+        self.select_item(selected);
+        self.initial_selection = Some(selected);
+    }
+
+    /// Saves a snapshot of the cursor position on the menu's history, to return to with
+    /// `pop_history`.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// let options = (1..=30).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+    /// let mut menu = Menu::new(options, MenuProps { page_size: Some(3), ..MenuProps::default() });
+    ///
+    /// menu.push_history();
+    /// menu.handle_key(Key::ArrowDown);
+    /// menu.push_history();
+    /// menu.handle_key(Key::ArrowRight);
+    ///
+    /// assert!(menu.pop_history());
+    /// assert_eq!(menu.snapshot().selected_option, 1);
+    /// assert!(menu.pop_history());
+    /// assert_eq!(menu.snapshot().selected_option, 0);
+    /// assert!(!menu.pop_history());
+    /// ```
+    pub fn push_history(&mut self) {
+        self.history.push(self.snapshot());
+    }
+
+    /// Restores the most recent snapshot saved with `push_history`, returning false if the
+    /// history is empty.
+    pub fn pop_history(&mut self) -> bool {
+        match self.history.pop() {
+            Some(snapshot) => {
+                self.restore_snapshot(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Updates the menu as if `key` was pressed while it was shown, and returns what the caller
+    /// should do next. Together with `key_events` and `draw_on_term`, this lets a menu be driven
+    /// from an existing event loop instead of by `show()`.
+    ///
+    /// Actions aren't run when an option is selected, so the caller can restore its own screen
+    /// first. Use `run_action` to run them.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuAction, MenuOption, MenuProps};
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("first", || {}),
+    ///     MenuOption::new("second", || {}),
+    ///     MenuOption::new("third", || {}),
+    /// ], MenuProps::default());
+    ///
+    /// assert_eq!(menu.handle_key(Key::ArrowDown), MenuAction::Navigate);
+    /// assert_eq!(menu.handle_key(Key::ArrowDown), MenuAction::Navigate);
+    /// assert_eq!(menu.handle_key(Key::Tab), MenuAction::NoOp);
+    /// assert_eq!(menu.handle_key(Key::Enter), MenuAction::Select(2));
+    /// assert_eq!(menu.handle_key(Key::Escape), MenuAction::Exit);
+    /// ```
+    pub fn handle_key(&mut self, key: Key) -> MenuAction {
+        if self.input.is_some() {
+            return self.handle_input_key(key);
+        }
+        let previous_selection = self.selected_item;
+        let command = if self.searching {
+            match key {
+                Key::ArrowUp => Some(Command::Up),
+                Key::ArrowDown => Some(Command::Down),
+                Key::Enter => Some(Command::Confirm),
+                _ => None,
+            }
+        } else {
+            self.key_bindings.command(&key, self.multi_select).or_else(|| {
+                let index = self.shortcut_index(&key)?;
+                self.select_item(index);
+                Some(Command::Confirm)
+            })
+        };
+        let command = match command {
+            Some(Command::Up | Command::Down) if self.layout == MenuLayout::Horizontal && !self.is_listing() => {
+                return MenuAction::NoOp;
+            }
+            Some(Command::Left) if self.layout == MenuLayout::Horizontal => Some(Command::Up),
+            Some(Command::Right) if self.layout == MenuLayout::Horizontal => Some(Command::Down),
+            // filtered options are listed without pages or columns, and the filter overrides search
+            Some(Command::Left | Command::Right | Command::PageUp | Command::PageDown | Command::Search)
+                if self.filter.is_some() => return MenuAction::NoOp,
+            command => command,
+        };
+        let action = match command {
+            Some(Command::Up) if self.wrap_mode.wraps_options() => {
+                let prev = (self.page_start..self.selected_item).rev().find(|&i| self.is_selectable(i))
+                    .or_else(|| (self.selected_item + 1..=self.page_end).rev().find(|&i| self.is_selectable(i)));
+                if let Some(prev) = prev {
+                    self.select_item(prev);
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::Down) if self.wrap_mode.wraps_options() => {
+                let next = (self.selected_item + 1..=self.page_end).find(|&i| self.is_selectable(i))
+                    .or_else(|| (self.page_start..self.selected_item).find(|&i| self.is_selectable(i)));
+                if let Some(next) = next {
+                    self.select_item(next);
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::Up) => {
+                let prev = (0..self.selected_item).rev().find(|&i| self.is_selectable(i))
+                    .or_else(|| self.last_selectable().filter(|_| self.wrap_mode.wraps_pages()));
+                if let Some(prev) = prev {
+                    self.select_item(prev);
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::Down) => {
+                let next = (self.selected_item + 1..self.items.len()).find(|&i| self.is_selectable(i))
+                    .or_else(|| self.first_selectable().filter(|_| self.wrap_mode.wraps_pages()));
+                if let Some(next) = next {
+                    self.select_item(next);
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::First) => {
+                if let Some(first) = self.first_selectable() {
+                    self.select_item(first);
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::Last) => {
+                if let Some(last) = self.last_selectable() {
+                    self.select_item(last);
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::PageUp) if self.selected_page != 0 => {
+                self.set_page(self.selected_page - 1);
+                MenuAction::Navigate
+            }
+            Some(Command::PageDown) if self.selected_page + 1 < self.num_pages => {
+                self.set_page(self.selected_page + 1);
+                MenuAction::Navigate
+            }
+            Some(Command::Left) if self.column_of(self.selected_item) > 0 => {
+                let target = self.selected_item - self.rows_on_page();
+                if let Some(prev) = (self.page_start..=target).rev().find(|&i| self.is_selectable(i)) {
+                    self.select_item(prev);
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::Right) if self.column_of(self.selected_item) + 1 < self.columns_on_page() => {
+                let target = (self.selected_item + self.rows_on_page()).min(self.page_end);
+                if let Some(next) = (target..=self.page_end).find(|&i| self.is_selectable(i)) {
+                    self.select_item(next);
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::Left) if self.selected_page != 0 => {
+                self.set_page(self.selected_page - 1);
+                MenuAction::Navigate
+            }
+            Some(Command::Right) if self.selected_page < self.num_pages - 1 => {
+                self.set_page(self.selected_page + 1);
+                MenuAction::Navigate
+            }
+            Some(Command::Left) if self.num_pages > 1 && self.wrap_mode.wraps_pages() => {
+                self.set_page(self.num_pages - 1);
+                MenuAction::Navigate
+            }
+            Some(Command::Right) if self.num_pages > 1 && self.wrap_mode.wraps_pages() => {
+                self.set_page(0);
+                MenuAction::Navigate
+            }
+            Some(Command::Exit) => MenuAction::Exit,
+            _ if key == Key::CtrlC => MenuAction::Exit,
+            Some(Command::Search) => {
+                self.searching = true;
+                self.search_query.clear();
+                MenuAction::Navigate
+            }
+            None if self.searching => {
+                self.update_search(&key);
+                MenuAction::Navigate
+            }
+            None => {
+                let Key::Char(c) = key else { return MenuAction::NoOp };
+                match self.find_next_matching(self.selected_item + 1, c) {
+                    Some(next) => {
+                        self.select_item(next);
+                        MenuAction::Navigate
+                    }
+                    None => MenuAction::NoOp,
+                }
+            }
+            Some(Command::Toggle) if self.is_selectable(self.selected_item) => {
+                let item = &mut self.items[self.selected_item];
+                item.checked = !item.checked;
+                MenuAction::Navigate
+            }
+            Some(Command::CheckPage) => {
+                for item in &mut self.items[self.page_start..=self.page_end] {
+                    item.checked |= !item.disabled && !item.separator && !item.section;
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::ClearChecked) => {
+                for item in &mut self.items {
+                    item.checked = false;
+                }
+                MenuAction::Navigate
+            }
+            Some(Command::Confirm) if !self.multi_select && self.is_selectable(self.selected_item)
+                && self.items[self.selected_item].text_input.is_some() => {
+                self.input = Some(InputState { index: self.selected_item, text: String::new() });
+                MenuAction::Navigate
+            }
+            Some(Command::Confirm) if !self.multi_select && self.is_selectable(self.selected_item)
+                && self.items[self.selected_item].on_toggle.is_some() => {
+                self.items[self.selected_item].toggle_checked_state();
+                MenuAction::Navigate
+            }
+            Some(Command::Confirm) if !self.multi_select && self.is_selectable(self.selected_item)
+                && self.items[self.selected_item].radio.is_some() => {
+                if self.items[self.selected_item].checked {
+                    return MenuAction::NoOp;
+                }
+                self.check_radio(self.selected_item);
+                MenuAction::Navigate
+            }
+            Some(Command::Confirm) if self.multi_select || self.is_selectable(self.selected_item) => {
+                MenuAction::Select(self.selected_item)
+            }
+            _ => MenuAction::NoOp,
+        };
+
+        if self.selected_item != previous_selection {
+            if let Some(on_select) = &self.items[self.selected_item].on_select {
+                on_select();
+            }
+        }
+        action
+    }
+
+    /// Edits the text of the text input option being typed into.
+    fn handle_input_key(&mut self, key: Key) -> MenuAction {
+        let Some(input) = &mut self.input else { return MenuAction::NoOp };
+        match key {
+            Key::Enter => {
+                let InputState { index, text } = self.input.take().unwrap();
+                if let Some(text_input) = &mut self.items[index].text_input {
+                    (text_input.on_submit)(text);
+                }
+            }
+            Key::Escape => self.input = None,
+            Key::CtrlC => {
+                self.input = None;
+                return MenuAction::Exit;
+            }
+            Key::Backspace => {
+                input.text.pop();
+            }
+            Key::Char(c) if !c.is_control() => {
+                let max_chars = self.items[input.index].text_input.as_ref().map_or(0, |text_input| text_input.max_chars);
+                if input.text.chars().count() < max_chars {
+                    input.text.push(c);
+                }
+            }
+            _ => return MenuAction::NoOp,
+        }
+        MenuAction::Navigate
+    }
+
+    /// Runs the `on_confirm` hook and the action of the option at `index`, as happens when it is
+    /// selected while the menu is shown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn run_action(&mut self, index: usize) {
+        let item = &mut self.items[index];
+        if let Some(on_confirm) = &item.on_confirm {
+            on_confirm();
+        }
+        (item.action)();
+    }
+
+    /// Draws the menu on the given terminal in its current state. Use this to redraw a menu
+    /// driven by `handle_key` after its state changed.
+    pub fn draw_on_term(&mut self, term: impl TerminalOutput) -> Result<(), MenuError> {
+        self.resize(term.size());
+        self.redraw(&term)
+    }
+
+    /// Draws the menu in its current state on a terminal of the given size, and returns what
+    /// would be written to it, without the sequence clearing the screen. Useful for comparing
+    /// the look of a menu in tests. The frame hook isn't run.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuOption, MenuProps};
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("tea", || {}),
+    ///     MenuOption::new("coffee", || {}),
+    /// ], MenuProps { title: "Drinks", no_color: true, compact: true, ..MenuProps::default() });
+    ///
+    /// let first = menu.render_to_string(20, 5);
+    /// assert_eq!(first, "\n      Drinks    \n      > tea     \n        coffee  \n");
+    ///
+    /// menu.handle_key(Key::ArrowDown);
+    /// let second = menu.render_to_string(20, 5);
+    /// assert_ne!(first, second);
+    /// assert!(second.contains("> coffee"));
+    /// ```
+    pub fn render_to_string(&mut self, term_width: u16, term_height: u16) -> String {
+        let term = StringTerm { output: RefCell::new(String::new()), size: (term_height, term_width) };
+        self.resize(term.size);
+        self.draw(&term).expect("writing to a string can't fail");
+        let output = term.output.into_inner();
+        output.strip_prefix(CLEAR_SCREEN).map(str::to_owned).unwrap_or(output)
+    }
+
+    /// Starts reading keys from stdout on a background thread, returning a channel that receives
+    /// each key pressed. Pass them to `handle_key` to drive the menu from another event loop.
+    ///
+    /// The thread stops once the receiver is dropped and another key is pressed, or if reading
+    /// from the terminal fails.
+    ///
+    /// ```no_run
+    /// # use console_menu::{Menu, MenuAction, MenuOption, MenuProps, Term};
+    /// let mut menu = Menu::new(vec![
+    ///     MenuOption::new("start", || {}),
+    ///     MenuOption::new("stop", || {}),
+    /// ], MenuProps::default());
+    ///
+    /// menu.draw_on_term(Term::stdout()).unwrap();
+    /// for key in menu.key_events() {
+    ///     match menu.handle_key(key) {
+    ///         MenuAction::Select(index) => {
+    ///             menu.run_action(index);
+    ///             break;
+    ///         }
+    ///         MenuAction::Exit => break,
+    ///         action if action.requires_redraw() => menu.draw_on_term(Term::stdout()).unwrap(),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn key_events(&self) -> mpsc::Receiver<Key> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let term = Term::stdout();
+            while let Ok(key) = TerminalOutput::read_key(&term) {
+                if sender.send(key).is_err() {
+                    break;
+                }
+            }
+        });
+        receiver
+    }
+
+    fn run_navigation(&mut self, stdout: &impl TerminalOutput) -> Result<Option<usize>, MenuError> {
+        let mut last_key = None;
+        let mut pressed_at = Instant::now();
+        let mut processed_at = Instant::now();
+        let reader = stdout.clone_for_thread().map(KeyReader::start);
+
+        loop {
+            let key = match &reader {
+                Some(reader) => self.wait_for_key(stdout, reader)?,
+                None => stdout.read_key()?,
+            };
+
+            let now = Instant::now();
+            if last_key.as_ref() == Some(&key) {
+                if now.duration_since(pressed_at) < self.key_repeat_delay
+                    || now.duration_since(processed_at) < self.key_repeat_rate {
+                    continue; // throttle held keys
+                }
+            } else {
+                last_key = Some(key.clone());
+                pressed_at = now;
+            }
+            processed_at = now;
+            self.resize(stdout.size());
+
+            match self.handle_key(key.clone()) {
+                MenuAction::Exit => {
+                    self.exit(stdout)?;
+                    if key == Key::CtrlC && !self.key_bindings.exit.contains(&key) {
+                        std::process::exit(130);
+                    }
+                    return Ok(None);
+                }
+                MenuAction::Select(index) if self.multi_select || self.pick_only => {
+                    self.exit(stdout)?;
+                    return Ok(Some(index));
+                }
+                MenuAction::Select(index) if self.exit_on_action && !self.multi_run_mode => {
+                    self.exit(stdout)?;
+                    self.run_action(index);
+                    return Ok(Some(index));
+                }
+                MenuAction::Select(index) => {
+                    let mut spinner = None;
+                    if self.show_loading {
+                        self.running_action = true;
+                        self.redraw(stdout)?;
+                        if let Some(term) = stdout.clone_for_thread() {
+                            spinner = Some(Spinner::start(term, self.spinner_frames(stdout.size())));
+                        }
+                    }
+                    self.run_action(index);
+                    drop(spinner);
+                    self.running_action = false;
+                }
+                MenuAction::Navigate | MenuAction::NoOp => {}
+            }
+            self.redraw(stdout)?;
+        }
+    }
+
+
+    /// The option on the current page selected by pressing a number key, if any.
+    fn shortcut_index(&self, key: &Key) -> Option<usize> {
+        if self.multi_select {
+            return None;
+        }
+        let Key::Char(c) = key else { return None };
+        let digit = c.to_digit(10).filter(|&digit| digit >= 1)? as usize;
+        let index = self.page_start + digit - 1;
+        Some(index).filter(|&index| index <= self.page_end && self.is_selectable(index))
+    }
+
+    /// Finds the first selectable option at or after `start` whose label starts with `ch`, ignoring
+    /// case and wrapping around to the first option.
+    fn find_next_matching(&self, start: usize, ch: char) -> Option<usize> {
+        let len = self.items.len();
+        (start..start + len).map(|i| i % len).find(|&i| {
+            self.is_selectable(i)
+                && self.items[i].label.chars().next().is_some_and(|first| first.to_lowercase().eq(ch.to_lowercase()))
+        })
+    }
+
+    /// Edits the search query with a key pressed while searching, keeping the selection on a
+    /// matching option.
+    fn update_search(&mut self, key: &Key) {
+        match key {
+            Key::Escape => {
+                self.searching = false;
+                self.search_query.clear();
+            }
+            Key::Backspace => {
+                self.search_query.pop();
+            }
+            Key::Char(c) if !c.is_control() => self.search_query.push(*c),
+            _ => return,
+        }
+        if !self.is_selectable(self.selected_item) {
+            if let Some(first) = self.first_selectable() {
+                self.select_item(first);
+            }
+        }
+    }
+
+    /// Updates the layout after options were added or removed, keeping the selection on a valid
+    /// option as close as possible to the previous one.
+    fn refresh_options(&mut self) {
+        if self.items.is_empty() {
+            self.items.push(MenuOption::default());
+        }
+        self.update_radio_groups();
+        self.update_layout();
+        self.selected_item = self.selected_item.min(self.items.len() - 1);
+        let selected = Some(self.selected_item).filter(|&i| self.is_selectable(i))
+            .or_else(|| (0..self.selected_item).rev().find(|&i| self.is_selectable(i)))
+            .or_else(|| self.first_selectable());
+        let index = selected.unwrap_or(0);
+        let page = self.pages.iter().position(|page| page.contains(&index)).unwrap_or(0);
+        self.set_page(page);
+        self.selected_item = index;
+    }
+
+    /// The ranges of options between separators and section headers.
+    fn option_groups(&self) -> Vec<Range<usize>> {
+        let mut groups = vec![];
+        let mut start = 0;
+        for (i, item) in self.items.iter().enumerate() {
+            if item.separator || item.section {
+                groups.push(start..i);
+                start = i + 1;
+            }
+        }
+        groups.push(start..self.items.len());
+        groups
+    }
+
+    /// Lays the options out again after they were moved around, selecting the first one.
+    fn reorder_options(&mut self) {
+        self.update_radio_groups();
+        self.update_layout();
+        self.set_page(0);
+    }
+
+    /// Records the checked option of each radio group, unchecking any others checked after it.
+    fn update_radio_groups(&mut self) {
+        self.radio_groups.clear();
+        for (i, item) in self.items.iter_mut().enumerate() {
+            let Some(radio) = &item.radio else { continue };
+            if item.checked && self.radio_groups.contains_key(&radio.group_id) {
+                item.checked = false;
+            } else if item.checked {
+                self.radio_groups.insert(radio.group_id.clone(), i);
+            }
+        }
+    }
+
+    /// Checks the radio button at `index` and unchecks the one checked before it in its group.
+    fn check_radio(&mut self, index: usize) {
+        let Some(radio) = &mut self.items[index].radio else { return };
+        if let Some(previous) = self.radio_groups.insert(radio.group_id.clone(), index) {
+            self.items[previous].checked = false;
+        }
+        let item = &mut self.items[index];
+        item.checked = true;
+        if let Some(radio) = &mut item.radio {
+            (radio.on_select)(&radio.value);
+        }
+    }
+
+    fn update_layout(&mut self) {
+        if self.layout == MenuLayout::Horizontal {
+            self.paginate_horizontal();
+            self.max_width = self.pages.iter()
+                .map(|page| self.row_width(page.clone()))
+                .max()
+                .unwrap_or(0);
+        } else {
+            // leave room for the page indicator in case the options don't fit on one page
+            let items_per_page = self.page_size.unwrap_or_else(|| {
+                (self.term_size.0 as usize).saturating_sub(self.extra_lines() + self.key_hint_rows() + 1) * self.columns
+            });
+            self.items_per_page = clamp(items_per_page, 1, self.items.len());
+            self.paginate();
+
+            self.column_width = self.items.iter().enumerate().fold(0, |max, (i, item)| {
+                let description_len = self.description_text(item).map_or(0, |d| text_width(&d));
+                max.max(self.label_width(i)).max(description_len)
+            });
+            let columns = self.columns.min(self.items.len());
+            self.max_width = self.column_width * columns + COLUMN_GAP * (columns - 1);
+        }
+        if self.show_scrollbar && self.num_pages > 1 {
+            self.max_width += SCROLLBAR_WIDTH;
+        }
+        // the last page number is the widest
+        if let Some(pagination) = self.pagination_text(self.num_pages.saturating_sub(1)) {
+            self.max_width = self.max_width.max(text_width(&pagination));
+        }
+        if let Some(breadcrumb) = &self.breadcrumb {
+            self.max_width = self.max_width.max(text_width(breadcrumb));
+        }
+        if let Some(title) = self.title.as_ref().filter(|_| self.header_renderer.is_none()) {
+            let mut title_len = text_width(&self.title_prefix) + text_width(title) + text_width(&self.title_suffix);
+            if self.has_title_box() {
+                title_len += 2;
+            }
+            if title_len > self.max_width {
+                self.max_width = title_len
+            }
+        }
+        if let Some(message) = self.message.as_ref().filter(|_| self.footer_renderer.is_none()) {
+            if text_width(message) > self.max_width {
+                self.max_width = text_width(message)
+            }
+        }
+        if self.footer_renderer.is_none() {
+            let tooltip_width = self.items.iter().filter_map(|item| item.tooltip.as_deref()).map(text_width).max();
+            self.max_width = self.max_width.max(tooltip_width.unwrap_or(0));
+            if self.show_loading {
+                self.max_width = self.max_width.max(text_width(&self.loading_message()));
+            }
+        }
+    }
+
+    /// Waits for the next key from `reader`, redrawing the menu whenever the terminal is resized
+    /// in the meantime.
+    fn wait_for_key(&mut self, stdout: &impl TerminalOutput, reader: &KeyReader) -> Result<Key, MenuError> {
+        reader.request();
+        loop {
+            match reader.keys.recv_timeout(RESIZE_POLL_INTERVAL) {
+                Ok(key) => return Ok(key?),
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    let term_size = stdout.size();
+                    if term_size != self.term_size {
+                        self.resize(term_size);
+                        self.redraw(stdout)?;
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(io::Error::new(io::ErrorKind::BrokenPipe, "key reader stopped").into());
+                }
+            }
+        }
+    }
+
+    /// Recomputes the layout if the terminal was resized since it was last laid out.
+    fn resize(&mut self, term_size: (u16, u16)) {
+        if term_size != self.term_size {
+            self.term_size = term_size;
+            self.refresh_options();
+        }
+    }
+
+    /// Splits the items into pages taking up at most `items_per_page` rows. A page is only allowed
+    /// to overflow to avoid showing nothing but separators.
+    fn paginate(&mut self) {
+        let only_separators = |items: &[MenuOption<T>]| items.iter().all(|item| item.separator || item.section);
+
+        self.pages.clear();
+        let mut start = 0;
+        while start < self.items.len() {
+            let mut end = start + 1;
+            let mut height = self.item_height(start);
+            while end < self.items.len() && height + self.item_height(end) <= self.items_per_page {
+                height += self.item_height(end);
+                end += 1;
+            }
+            while end < self.items.len() && only_separators(&self.items[start..end]) {
+                end += 1;
+            }
+            // move section headers at the bottom of the page to the next page with their options
+            let mut headers_end = end;
+            while headers_end < self.items.len() && headers_end > start + 1 && self.items[headers_end - 1].section {
+                headers_end -= 1;
+            }
+            if headers_end < end && !only_separators(&self.items[start..headers_end]) {
+                end = headers_end;
+            }
+            self.pages.push(start..end);
+            start = end;
+        }
+        if self.pages.len() > 1 && only_separators(&self.items[self.pages[self.pages.len() - 1].clone()]) {
+            let last = self.pages.pop().unwrap();
+            self.pages.last_mut().unwrap().end = last.end;
+        }
+        self.num_pages = self.pages.len();
+    }
+
+    /// The widest an option's label can be drawn, whether it's selected or not.
+    fn label_width(&self, index: usize) -> usize {
+        let item = &self.items[index];
+        match &self.option_renderer {
+            Some(renderer) if !item.separator && !item.section => {
+                text_width(&renderer(item, false)).max(text_width(&renderer(item, true)))
+            }
+            _ => {
+                let width = text_width(&self.item_label(index, false)).max(text_width(&self.item_label(index, true)));
+                // leave room for the longest text that can be typed and the cursor
+                match &item.text_input {
+                    Some(text_input) => width.max(width - text_width(&text_input.placeholder) + text_input.max_chars + 1),
+                    None => width,
+                }
+            }
+        }
+    }
+
+    /// The width of the given options drawn side by side in the horizontal layout.
+    fn row_width(&self, range: Range<usize>) -> usize {
+        let separators = text_width(&self.separator_str) * range.len().saturating_sub(1);
+        range.map(|i| self.label_width(i)).sum::<usize>() + separators
+    }
+
+    /// Splits the items into pages holding as many options as fit side by side in the terminal,
+    /// and at most `page_size` options if it is set.
+    fn paginate_horizontal(&mut self) {
+        let max_width = (self.term_size.1 as usize).saturating_sub(4);
+        let max_len = self.page_size.unwrap_or(usize::MAX).max(1);
+
+        self.pages.clear();
+        let mut start = 0;
+        while start < self.items.len() {
+            let mut end = start + 1;
+            while end < self.items.len() && end - start < max_len && self.row_width(start..end + 1) <= max_width {
+                end += 1;
+            }
+            self.pages.push(start..end);
+            start = end;
+        }
+        self.num_pages = self.pages.len();
+        self.items_per_page = self.pages.iter().map(|page| page.len()).max().unwrap_or(1);
+    }
+
+    /// Number of rows the options on a page take up. Search results are listed one per row in
+    /// both layouts.
+    fn content_rows(&self) -> usize {
+        match self.layout {
+            MenuLayout::Horizontal if self.is_listing() => {
+                let rows = (self.term_size.0 as usize).saturating_sub(self.extra_lines() + self.key_hint_rows() + 1);
+                clamp(rows, 1, self.items_per_page)
+            }
+            MenuLayout::Horizontal => 1,
+            MenuLayout::Vertical => self.items_per_page.div_ceil(self.columns),
+        }
+    }
+
+    /// Number of rows an option takes up when paginating. Options with a description take up two
+    /// rows, unless the page size is fixed or the options are in columns.
+    fn item_height(&self, index: usize) -> usize {
+        if self.page_size.is_none() && self.columns == 1 && self.items[index].description.is_some() {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Number of rows needed to show the current page.
+    fn rows_on_page(&self) -> usize {
+        (self.page_end - self.page_start + 1).div_ceil(self.columns)
+    }
+
+    fn columns_on_page(&self) -> usize {
+        (self.page_end - self.page_start + 1).div_ceil(self.rows_on_page())
+    }
+
+    /// The column an option on the current page is shown in.
+    fn column_of(&self, index: usize) -> usize {
+        (index - self.page_start) / self.rows_on_page()
+    }
+
+    fn item_label(&self, index: usize, selected: bool) -> String {
+        format!("{}{}{}", self.item_prefix(index, selected), self.item_text(&self.items[index]), self.input_text(index))
+    }
+
+    /// The text shown after the label of a text input option: the text being typed with a cursor
+    /// while it's edited, or the dimmed placeholder otherwise.
+    fn input_text(&self, index: usize) -> String {
+        let Some(text_input) = &self.items[index].text_input else {
+            return String::new();
+        };
+        match &self.input {
+            Some(input) if input.index == index => format!(": {}▏", input.text),
+            _ => format!(": {}", self.apply_dim(&text_input.placeholder)),
+        }
+    }
+
+    /// The cursor and checkbox displayed before an option's label.
+    fn item_prefix(&self, index: usize, selected: bool) -> String {
+        let item = &self.items[index];
+        if item.separator || item.section {
+            return String::new();
+        }
+        let mut prefix = self.cursor(selected);
+        if self.show_shortcuts {
+            let position = index - self.page_of(index).start + 1;
+            if position <= 9 {
+                prefix.push_str(&format!("{}. ", position));
+            } else {
+                prefix.push_str("   ");
+            }
+        }
+        if self.multi_select || item.on_toggle.is_some() {
+            let mark = if item.checked { self.check_char } else { self.uncheck_char };
+            prefix.push_str(&format!("[{}] ", mark));
+        } else if item.radio.is_some() {
+            prefix.push_str(if item.checked { "(•) " } else { "( ) " });
+        }
+        prefix
+    }
+
+    /// The text marking the selected option, or the matching blank text for other options.
+    fn cursor(&self, selected: bool) -> String {
+        match &self.selection_prefix {
+            Some(selection_prefix) if selected => selection_prefix.clone(),
+            Some(selection_prefix) => self.normal_prefix.clone()
+                .unwrap_or_else(|| " ".repeat(text_width(selection_prefix))),
+            None if self.no_color => if selected { "> " } else { "  " }.to_owned(),
+            None => String::new(),
+        }
+    }
+
+    /// The label of an option as displayed, shortened to `max_label_width`.
+    fn item_text(&self, item: &MenuOption<T>) -> String {
+        if item.separator {
+            return String::new();
+        }
+        match self.max_label_width {
+            Some(max) => truncate(&item.label, max),
+            None => item.label.clone(),
+        }
+    }
+
+    /// The text of an option as drawn, which is refreshed for options with a live label. The
+    /// layout is computed from `item_text`, so refreshed text is fit to the same width.
+    fn live_text(&self, item: &MenuOption<T>) -> String {
+        let text = self.item_text(item);
+        let Some(refresh) = &item.label_refresh else {
+            return text;
+        };
+        let width = text_width(&text);
+        let refreshed = truncate(&refresh(), width);
+        pad_right(refreshed.clone(), text_width(&refreshed), width)
+    }
+
+    fn is_selectable(&self, index: usize) -> bool {
+        let item = &self.items[index];
+        !item.disabled && !item.separator && !item.section && self.is_visible(index)
+    }
+
+    /// Whether the option at `index` passes the filter, or matches the search query while
+    /// searching without a filter.
+    fn is_visible(&self, index: usize) -> bool {
+        let item = &self.items[index];
+        match &self.filter {
+            Some(filter) => filter(item),
+            None => !self.searching || find_match(&item.label, &self.search_query).is_some(),
+        }
+    }
+
+    /// Whether the options are listed one per row as search results or filtered options, rather
+    /// than laid out in pages.
+    fn is_listing(&self) -> bool {
+        self.searching || self.filter.is_some()
+    }
+
+    fn first_selectable(&self) -> Option<usize> {
+        (0..self.items.len()).find(|&i| self.is_selectable(i))
+    }
+
+    fn last_selectable(&self) -> Option<usize> {
+        (0..self.items.len()).rev().find(|&i| self.is_selectable(i))
+    }
+
+    /// The first selectable option from `index` onwards, or the closest one before it.
+    fn closest_selectable(&self, index: usize) -> usize {
+        (index..self.items.len()).find(|&i| self.is_selectable(i))
+            .or_else(|| (0..index).rev().find(|&i| self.is_selectable(i)))
+            .unwrap_or(index)
+    }
+
+    /// The range of options on the page containing `index`.
+    fn page_of(&self, index: usize) -> Range<usize> {
+        self.pages.iter().find(|page| page.contains(&index)).cloned().unwrap_or(index..index + 1)
+    }
+
+    fn select_item(&mut self, index: usize) {
+        let page = self.pages.iter().position(|page| page.contains(&index)).unwrap();
+        if page != self.selected_page {
+            self.set_page(page);
+        }
+        self.selected_item = index;
+    }
+
+    fn set_page(&mut self, page: usize) {
+        if page != self.selected_page {
+            if let Some(callback) = &self.page_change_callback {
+                callback(page);
+            }
+        }
+        self.selected_page = page;
+        self.page_start = self.pages[page].start;
+        self.page_end = self.pages[page].end - 1;
+        self.selected_item = (self.page_start..=self.page_end)
+            .find(|&i| self.is_selectable(i))
+            .unwrap_or(self.page_start);
+    }
+
+    fn redraw(&mut self, stdout: &impl TerminalOutput) -> Result<(), MenuError> {
+        if let Some(hook) = &self.frame_hook {
+            hook(self, self.frame);
+        }
+        self.frame += 1;
+        self.draw(stdout)
+    }
+
+    fn draw(&self, stdout: &impl TerminalOutput) -> Result<(), MenuError> {
+        stdout.clear_screen()?;
+
+        let menu_width = self.max_width;
+        let mut extra_lines = self.extra_lines();
+        if self.pagination_text(self.selected_page).is_some() {
+            extra_lines += 1;
+        }
+
+        let (term_rows, term_cols) = stdout.size();
+        let menu_rows = (term_rows as usize).saturating_sub(self.key_hint_rows());
+        let indent = self.horizontal_padding.resolve(term_cols as usize).unwrap_or_else(|| match self.menu_align {
+            MenuAlign::Center => ((term_cols / 2) as usize).saturating_sub((menu_width + 4) / 2),
+            MenuAlign::Left => 0,
+            MenuAlign::Right => (term_cols as usize).saturating_sub(menu_width + 4),
+            MenuAlign::Fixed(indent) => indent,
+        });
+        let indent_str = pad_left(String::new(), 0, indent);
+
+        let menu_height = self.content_rows() + extra_lines;
+        let vertical_pad = self.vertical_padding.resolve(menu_rows).unwrap_or_else(|| match self.menu_valign {
+            MenuVAlign::Top => 0,
+            MenuVAlign::Center => (menu_rows / 2).saturating_sub(menu_height / 2),
+            // leave the last row empty so the final newline doesn't scroll the menu
+            MenuVAlign::Bottom => menu_rows.saturating_sub(menu_height + 1),
+        });
+        stdout.write_str(&format!("{:\n<width$}", "", width=vertical_pad))?;
+
+        if !self.no_color {
+            stdout.write_str(&self.fg_escape(self.fg_color))?; // set foreground color
+        }
+        if self.has_edge_rows() {
+            stdout.write_line(&format!("{}{}", indent_str, self.edge_row(true, menu_width)))?;
+        }
+
+        if let Some(breadcrumb) = &self.breadcrumb {
+            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&self.apply_dim(breadcrumb), menu_width)))?;
+        }
+        if let Some(header) = &self.header_renderer {
+            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&header(), menu_width)))?;
+        } else if let Some(title) = &self.title {
+            let title_str = format!("{}{}{}", self.title_prefix, self.apply_underline(&self.apply_bold(title)), self.title_suffix);
+            if let Some(border) = self.border_style.chars().filter(|_| self.title_border) {
+                let title_str = self.align(title_str, menu_width.saturating_sub(2), |free| self.title_align.padding(free));
+                let title_str = pad_right(title_str.clone(), text_width(&title_str), menu_width.saturating_sub(2));
+                let edge = self.switch_fg(&border.vertical.to_string(), self.border_color);
+                let title_row = format!("{}{}{}", edge, self.switch_fg(&title_str, self.title_color), edge);
+                stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&self.title_box_edge(true, menu_width), menu_width)))?;
+                stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&title_row, menu_width)))?;
+                stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&self.title_box_edge(false, menu_width), menu_width)))?;
+            } else {
+                let title_str = self.align(title_str, menu_width, |free| self.title_align.padding(free));
+                stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&self.switch_fg(&title_str, self.title_color), menu_width)))?;
+            }
+            if !self.compact {
+                stdout.write_line(&format!("{}{}", indent_str, self.apply_bg("", menu_width)))?;
+            }
+        }
+
+        let content_width = if self.has_scrollbar() { menu_width - SCROLLBAR_WIDTH } else { menu_width };
+        let mut lines = vec![];
+        if self.is_listing() {
+            let matches: Vec<usize> = (0..self.items.len())
+                .filter(|&i| if self.searching { self.is_selectable(i) } else { self.is_visible(i) })
+                .collect();
+            let rows = self.content_rows();
+            let position = matches.iter().position(|&i| i == self.selected_item).unwrap_or(0);
+            for &i in matches.iter().skip((position + 1).saturating_sub(rows)).take(rows) {
+                lines.push(self.option_str(i, content_width));
+            }
+        } else if self.layout == MenuLayout::Horizontal {
+            let cells: Vec<String> = (self.page_start..=self.page_end)
+                .map(|i| {
+                    let option_str = self.option_str(i, self.label_width(i));
+                    pad_right(option_str.clone(), text_width(&option_str), self.label_width(i))
+                })
+                .collect();
+            lines.push(cells.join(&self.separator_str));
+        } else if self.columns == 1 {
+            for i in self.page_start..=self.page_end {
+                lines.push(self.option_str(i, content_width));
+                if let Some(description) = self.description_str(i) {
+                    lines.push(description);
+                }
+            }
+        } else {
+            let rows = self.rows_on_page();
+            for row in 0..rows {
+                let cells: Vec<String> = (self.page_start + row..=self.page_end).step_by(rows)
+                    .map(|i| {
+                        let option_str = self.option_str(i, self.column_width);
+                        pad_right(option_str.clone(), text_width(&option_str), self.column_width)
+                    })
+                    .collect();
+                lines.push(cells.join(&" ".repeat(COLUMN_GAP)));
+            }
+        }
+        let scrollbar = if self.has_scrollbar() { self.scrollbar(lines.len()) } else { vec![] };
+        for (row, line) in lines.into_iter().enumerate() {
+            let line = match scrollbar.get(row) {
+                Some(bar) => format!("{} {}", pad_right(line.clone(), text_width(&line), content_width), bar),
+                None => line,
+            };
+            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&line, menu_width)))?;
+        }
+
+        if self.searching {
+            let prompt = format!("/{}", self.search_query);
+            let skip = text_width(&prompt).saturating_sub(menu_width);
+            let prompt: String = prompt.chars().skip(skip).collect();
+            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&prompt, menu_width)))?;
+        } else if let Some(pagination) = self.pagination_text(self.selected_page) {
+            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&pagination, menu_width)))?;
+        }
+        if let Some(footer) = &self.footer_renderer {
+            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&footer(), menu_width)))?;
+        } else if self.has_message() {
+            let loading_message = self.loading_message();
+            let message = if self.running_action {
+                Some(loading_message.as_str())
+            } else {
+                self.items[self.selected_item].tooltip.as_deref().or(self.message.as_deref())
+            };
+            let message = message.map_or(String::new(), |message| self.switch_fg(message, self.msg_color));
+            let message = self.align(message, menu_width, |free| self.msg_align.padding(free));
+            if !self.compact {
+                stdout.write_line(&format!("{}{}", indent_str, self.apply_bg("", menu_width)))?;
+            }
+            stdout.write_line(&format!("{}{}", indent_str, self.apply_bg(&message, menu_width)))?;
+        }
+
+        if self.has_edge_rows() {
+            stdout.write_line(&format!("{}{}", indent_str, self.edge_row(false, menu_width)))?;
+        }
+        if !self.no_color {
+            stdout.write_str("\x1b[39m")?; // reset foreground color
+        }
+
+        if self.show_key_hints {
+            // the hints go on the last row, without a newline so the menu doesn't scroll up
+            let rows_below = (term_rows as usize).saturating_sub(vertical_pad + menu_height + 1);
+            stdout.write_str(&"\n".repeat(rows_below))?;
+            stdout.write_str(&self.key_hints())?;
+        }
+
+        stdout.flush()?;
+        Ok(())
+    }
+
+    /// The key hints shown below the menu, listing the first key bound to each command.
+    fn key_hints(&self) -> String {
+        let bindings = &self.key_bindings;
+        let (prev, next) = match self.layout {
+            MenuLayout::Vertical => (&bindings.up, &bindings.down),
+            MenuLayout::Horizontal => (&bindings.left, &bindings.right),
+        };
+        let navigate = prev.first().zip(next.first()).map(|(prev, next)| format!("{}/{}", key_name(prev), key_name(next)));
+        let mut hints = vec![(navigate, "Navigate")];
+        if self.multi_select {
+            hints.push((bindings.toggle.first().map(key_name), "Toggle"));
+        }
+        hints.push((bindings.confirm.first().map(key_name), "Confirm"));
+        hints.push((bindings.search.first().map(key_name), "Search"));
+        hints.push((bindings.exit.first().map(key_name), "Exit"));
+
+        hints.into_iter()
+            .filter_map(|(key, description)| Some(format!("{} {}", self.apply_bold(&key?), description)))
+            .collect::<Vec<_>>()
+            .join("   ")
+    }
+
+
+    /// The styled text of an option, with separators filling `width` columns.
+    fn option_str(&self, index: usize, width: usize) -> String {
+        let option = &self.items[index];
+        let selected = index == self.selected_item;
+        let label = if self.searching {
+            let color = if selected { self.selected_color } else { self.fg_color };
+            let text = self.apply_option_style(&self.highlight_match(&self.live_text(option), color), option.style);
+            format!("{}{}{}", self.item_prefix(index, selected), text, self.input_text(index))
+        } else {
+            let text = self.apply_option_style(&self.live_text(option), option.style);
+            format!("{}{}{}", self.item_prefix(index, selected), text, self.input_text(index))
+        };
+        if option.separator {
+            let char_width = self.separator_char.width().unwrap_or(1).max(1);
+            self.separator_char.to_string().repeat(width / char_width)
+        } else if option.section {
+            self.switch_fg(&self.apply_bold(&label), self.title_color)
+        } else if let Some(renderer) = &self.option_renderer {
+            renderer(option, selected)
+        } else if option.disabled {
+            match self.disabled_color {
+                Some(color) => self.switch_fg(&label, color),
+                None => self.apply_dim(&label),
+            }
+        } else if selected {
+            self.switch_bg(&self.switch_fg(&self.apply_selected_bold(&label), self.selected_color), option.bg_color)
+        } else if let Some(color) = option.color {
+            self.switch_bg(&self.switch_fg(&label, color), option.bg_color)
+        } else {
+            self.switch_bg(&label, option.bg_color)
+        }
+    }
+
+    /// Highlights the part of `text` matching the search query. `color` is the color the rest of
+    /// the text is drawn in.
+    fn highlight_match(&self, text: &str, color: ColorMode) -> String {
+        let Some(range) = find_match(text, &self.search_query).filter(|range| !range.is_empty()) else {
+            return text.to_owned();
+        };
+        let matched = match self.match_color {
+            Some(match_color) if !self.no_color => {
+                format!("{}{}{}", self.fg_escape(match_color), &text[range.clone()], self.fg_escape(color))
+            }
+            _ => self.apply_underline(&text[range.clone()]),
+        };
+        format!("{}{}{}", &text[..range.start], matched, &text[range.end..])
+    }
+
+    /// The styled description line of an option, if it has one.
+    fn description_str(&self, index: usize) -> Option<String> {
+        let option = &self.items[index];
+        let description = self.description_text(option)?;
+        Some(if option.disabled {
+            match self.disabled_color {
+                Some(color) => self.switch_fg(&description, color),
+                None => self.apply_dim(&description),
+            }
+        } else if index == self.selected_item {
+            self.switch_fg(&self.apply_selected_bold(&description), self.selected_color)
+        } else {
+            self.switch_fg(&description, self.msg_color)
+        })
+    }
+
+    /// The unstyled description line of an option, indented below its label.
+    fn description_text(&self, item: &MenuOption<T>) -> Option<String> {
+        let description = item.description.as_ref().filter(|_| self.columns == 1 && !item.separator && !item.section)?;
+        let description = match self.max_label_width {
+            Some(max) => truncate(description, max),
+            None => description.clone(),
+        };
+        let indent = text_width(&self.cursor(false)) + 2;
+        Some(format!("{}{}", " ".repeat(indent), description))
+    }
+
+    fn apply_bold(&self, s: &str) -> String {
+        self.apply_style(s, ansi::BOLD_ON, ansi::BOLD_OFF)
+    }
+
+    fn apply_selected_bold(&self, s: &str) -> String {
+        if self.selection_prefix.is_some() && !self.bold_selection {
+            return s.to_owned();
+        }
+        self.apply_bold(s)
+    }
+
+    fn apply_underline(&self, s: &str) -> String {
+        self.apply_style(s, ansi::UNDERLINE_ON, ansi::UNDERLINE_OFF)
+    }
+
+    fn apply_dim(&self, s: &str) -> String {
+        self.apply_style(s, ansi::DIM_ON, ansi::DIM_OFF)
+    }
+
+    fn apply_option_style(&self, s: &str, style: OptionStyle) -> String {
+        let mut s = s.to_owned();
+        let styles = [
+            (style.bold, ansi::BOLD_ON, ansi::BOLD_OFF),
+            (style.italic, ansi::ITALIC_ON, ansi::ITALIC_OFF),
+            (style.underline, ansi::UNDERLINE_ON, ansi::UNDERLINE_OFF),
+            (style.strikethrough, ansi::STRIKETHROUGH_ON, ansi::STRIKETHROUGH_OFF),
+        ];
+        for (_, on, off) in styles.into_iter().filter(|&(enabled, _, _)| enabled) {
+            s = self.apply_style(&s, on, off);
+        }
+        s
+    }
+
+    fn apply_style(&self, s: &str, on: &str, off: &str) -> String {
+        if self.no_color {
+            return s.to_owned();
+        }
+        format!("{}{}{}", on, s, off)
+    }
+
+    fn switch_fg(&self, s: &str, color: ColorMode) -> String {
+        if self.no_color {
+            return s.to_owned();
+        }
+        format!("{}{}{}", self.fg_escape(color), s, self.fg_escape(self.fg_color))
+    }
+
+    /// Draws `s` on the given background color, if any, returning to the menu's background after.
+    fn switch_bg(&self, s: &str, color: Option<ColorMode>) -> String {
+        match color {
+            Some(color) if !self.no_color => format!("{}{}{}", self.bg_escape(color), s, self.bg_escape(self.bg_color)),
+            _ => s.to_owned(),
+        }
+    }
+
+    fn apply_bg(&self, s: &str, width: usize) -> String {
+        let line = match self.border_style.chars() {
+            Some(border) => {
+                let edge = self.switch_fg(&border.vertical.to_string(), self.border_color);
+                format!("{} {} {}", edge, pad_right(s.to_owned(), text_width(s), width), edge)
+            }
+            None => pad_right(format!("  {}", s), text_width(s) + 2, width + 4),
+        };
+        self.fill_bg(line)
+    }
+
+    /// Moves `s` right by the number of spaces `padding` returns for the columns it leaves empty
+    /// in a row `width` columns wide.
+    fn align(&self, s: String, width: usize, padding: impl Fn(usize) -> usize) -> String {
+        let display_width = text_width(&s);
+        let padding = padding(width.saturating_sub(display_width));
+        pad_left(s, display_width, display_width + padding)
+    }
+
+    /// Number of rows drawn besides the options and page indicator.
+    fn extra_lines(&self) -> usize {
+        let spacing = if self.compact { 0 } else { 1 };
+        let mut extra_lines = if self.has_edge_rows() { 2 } else { 0 };
+        if self.breadcrumb.is_some() {
+            extra_lines += 1;
+        }
+        if self.header_renderer.is_some() {
+            extra_lines += 1;
+        } else if self.title.is_some() {
+            extra_lines += 1 + spacing;
+            if self.has_title_box() {
+                extra_lines += 2;
+            }
+        }
+        if self.footer_renderer.is_some() {
+            extra_lines += 1;
+        } else if self.has_message() {
+            extra_lines += 1 + spacing;
+        }
+        extra_lines
+    }
+
+    /// The text of the row below the options while `page` is selected, or None if there isn't one.
+    fn pagination_text(&self, page: usize) -> Option<String> {
+        let format = self.pagination_format.as_ref()
+            .filter(|_| self.num_pages > 1 && !self.show_scrollbar && self.filter.is_none())?;
+        Some(format.replace("{page}", &(page + 1).to_string()).replace("{total}", &self.num_pages.to_string()))
+    }
+
+    fn has_scrollbar(&self) -> bool {
+        self.show_scrollbar && self.num_pages > 1 && !self.is_listing()
+    }
+
+    /// The scrollbar characters for each of `rows` rows, with a thumb sized by the fraction of
+    /// pages shown and placed at the current page.
+    fn scrollbar(&self, rows: usize) -> Vec<char> {
+        let thumb = (rows / self.num_pages).max(1);
+        let last_page = self.num_pages - 1;
+        let start = (self.selected_page * (rows - thumb) + last_page / 2) / last_page;
+        (0..rows).map(|row| if (start..start + thumb).contains(&row) { '█' } else { '░' }).collect()
+    }
+
+    /// The loading indicator shown while an action runs, at the current spinner frame.
+    fn loading_message(&self) -> String {
+        format!("{} {}", SPINNER_FRAMES[self.spinner_frame], LOADING_MESSAGE)
+    }
+
+    /// Every frame of the menu while an action runs, one for each spinner character, drawn for a
+    /// terminal of the given size.
+    fn spinner_frames(&mut self, term_size: (u16, u16)) -> Vec<String> {
+        let frames = (0..SPINNER_FRAMES.len()).map(|frame| {
+            self.spinner_frame = frame;
+            let term = StringTerm { output: RefCell::new(String::new()), size: term_size };
+            self.draw(&term).expect("writing to a string can't fail");
+            let output = term.output.into_inner();
+            output.strip_prefix(CLEAR_SCREEN).map(str::to_owned).unwrap_or(output)
+        }).collect();
+        self.spinner_frame = 0;
+        frames
+    }
+
+    /// Whether a row is needed for the message or option tooltips.
+    fn has_message(&self) -> bool {
+        self.message.is_some() || self.show_loading || self.items.iter().any(|item| item.tooltip.is_some())
+    }
+
+    /// Number of rows at the bottom of the terminal taken up by key hints.
+    fn key_hint_rows(&self) -> usize {
+        if self.show_key_hints { 1 } else { 0 }
+    }
+
+    fn has_edge_rows(&self) -> bool {
+        !self.compact || self.border_style != BorderStyle::None
+    }
+
+    /// The top or bottom row of the menu, which holds the border if there is one.
+    fn edge_row(&self, top: bool, width: usize) -> String {
+        let Some(border) = self.border_style.chars() else {
+            return self.apply_bg("", width);
+        };
+        let (left, right) = if top {
+            (border.top_left, border.top_right)
+        } else {
+            (border.bottom_left, border.bottom_right)
+        };
+        let line = format!("{}{}{}", left, border.horizontal.to_string().repeat(width + 2), right);
+        self.fill_bg(self.switch_fg(&line, self.border_color))
+    }
+
+    /// Whether the title is drawn in its own box.
+    fn has_title_box(&self) -> bool {
+        self.title_border && self.border_style != BorderStyle::None && self.header_renderer.is_none()
+    }
+
+    /// The top or bottom row of the box around the title, `width` columns wide.
+    fn title_box_edge(&self, top: bool, width: usize) -> String {
+        let Some(border) = self.border_style.chars() else {
+            return String::new();
+        };
+        let (left, right) = if top {
+            (border.top_left, border.top_right)
+        } else {
+            (border.bottom_left, border.bottom_right)
+        };
+        let line = format!("{}{}{}", left, border.horizontal.to_string().repeat(width.saturating_sub(2)), right);
+        self.switch_fg(&line, self.border_color)
+    }
+
+    fn fill_bg(&self, line: String) -> String {
+        if self.no_color {
+            return line;
+        }
+        format!("{}{}\x1b[49m", self.bg_escape(self.bg_color), line)
+    }
+
+    fn fg_escape(&self, color: ColorMode) -> String {
+        match self.color_support {
+            Some(support) => color.downgrade(support).fg_escape(),
+            None => color.fg_escape(),
+        }
+    }
+
+    fn bg_escape(&self, color: ColorMode) -> String {
+        match self.color_support {
+            Some(support) => color.downgrade(support).bg_escape(),
+            None => color.bg_escape(),
+        }
+    }
+
+
+    /// The terminal the menu is displayed on by default, stderr if `render_to_stderr` is set.
+    fn default_term(&self) -> Term {
+        if self.render_to_stderr {
+            Term::buffered_stderr()
+        } else {
+            Term::buffered_stdout()
+        }
+    }
+
+    fn exit(&self, stdout: &impl TerminalOutput) -> Result<(), MenuError> {
+        if self.clear_on_exit {
+            stdout.clear_screen()?;
+        } else if self.show_key_hints {
+            stdout.write_str("\n")?; // the hints row has no newline of its own
+        }
+        stdout.show_cursor()?;
+        stdout.write_str(self.screen_mode.leave_sequence())?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+impl Menu {
+    /// Creates a menu of options with no action from a list of labels, using the default
+    /// properties. Useful for menus that only display information.
+    ///
+    /// ```
+    /// # use console_menu::Menu;
+    /// let menu = Menu::from_labels(["red", "green", "blue"]);
+    /// assert_eq!(menu.items_on_current_page(), 3);
+    /// ```
+    pub fn from_labels(labels: impl IntoIterator<Item = impl AsRef<str>>) -> Menu {
+        labels.into_iter().map(|label| MenuOption::new(label.as_ref(), || {})).collect()
+    }
+
+    /// Asks a yes or no question, returning Some(true) for yes, Some(false) for no, or None if
+    /// the user exited.
+    ///
+    /// ```no_run
+    /// # use console_menu::{Menu, MenuProps};
+    /// if Menu::confirm("Delete all files?", MenuProps::default()) == Some(true) {
+    ///     println!("deleting");
+    /// }
+    /// ```
+    pub fn confirm<'a>(prompt: &'a str, props: MenuProps<'a>) -> Option<bool> {
+        Menu::confirm_with_options(prompt, "Yes", "No", props)
+    }
+
+    /// Asks a question like `confirm`, with custom labels for the yes and no options.
+    pub fn confirm_with_options<'a>(prompt: &'a str, yes_label: &'a str, no_label: &'a str, props: MenuProps<'a>) -> Option<bool> {
+        Menu::confirm_dialog(prompt, yes_label, no_label, props).show()
+    }
+
+    /// Creates the menu shown by `confirm_with_options`, whose options have the values true and
+    /// false. `prompt` replaces the title, and the menu is compact to keep the dialog small.
+    ///
+    /// ```
+    /// # use console_menu::{Key, Menu, MenuProps};
+    /// # use console_menu::testing::MockTerm;
+    /// let answer = |keys| {
+    ///     let term = MockTerm::new(keys);
+    ///     let mut dialog = Menu::confirm_dialog("Overwrite?", "Overwrite", "Keep", MenuProps::default());
+    ///     dialog.show_on_term(&term).map(|index| dialog.page_options(0).unwrap()[index].value.unwrap())
+    /// };
+    ///
+    /// assert_eq!(answer(vec![Key::Enter]), Some(true));
+    /// assert_eq!(answer(vec![Key::ArrowDown, Key::Enter]), Some(false));
+    /// assert_eq!(answer(vec![Key::Escape]), None);
+    /// ```
+    pub fn confirm_dialog<'a>(prompt: &'a str, yes_label: &'a str, no_label: &'a str, props: MenuProps<'a>) -> Menu<bool> {
+        Menu::new(vec![
+            MenuOption::with_value(yes_label, true),
+            MenuOption::with_value(no_label, false),
+        ], MenuProps { title: prompt, compact: true, ..props })
+    }
+}
+
+/// Shows the title, the options and the current selection.
+///
+/// ```
+/// # use console_menu::{Menu, MenuOption, MenuProps};
+/// let menu = Menu::new(vec![
+///     MenuOption::with_value("small", 8),
+///     MenuOption::with_value("large", 32),
+/// ], MenuProps { title: "Sizes", ..MenuProps::default() });
+///
+/// let debug = format!("{:?}", menu);
+/// assert!(debug.contains("Sizes"));
+/// assert!(debug.contains("\"small\"") && debug.contains("\"large\""));
+/// assert!(debug.contains("selected_item: 0"));
+/// ```
+impl<T: std::fmt::Debug> std::fmt::Debug for Menu<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Menu")
+            .field("title", &self.title)
+            .field("message", &self.message)
+            .field("options", &self.items)
+            .field("selected_item", &self.selected_item)
+            .field("selected_page", &self.selected_page)
+            .field("num_pages", &self.num_pages)
+            .field("multi_select", &self.multi_select)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Collects options into a menu with the default properties. Collecting no options results in a
+/// menu with only the default exit option.
+///
+/// ```
+/// # use console_menu::{Menu, MenuOption, MenuProps};
+/// let menu: Menu<u32> = (1..=5).map(|i| MenuOption::with_value(&i.to_string(), i)).collect();
+/// assert_eq!(menu.page_options(0).unwrap().len(), 5);
+/// assert!(menu.page_options(1).is_none());
+/// ```
+impl<T> FromIterator<MenuOption<T>> for Menu<T> {
+    fn from_iter<I: IntoIterator<Item = MenuOption<T>>>(iter: I) -> Self {
+        Menu::from_iter_with_props(iter, MenuProps::default())
+    }
+}
+
+
+/// Redraws the frames of the loading indicator in turn on a background thread, until dropped.
+struct Spinner {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Spinner {
+    /// Starts drawing `frames` on `term`, beginning with the second as the first is already on
+    /// screen.
+    fn start(term: Box<dyn TerminalOutput + Send>, frames: Vec<String>) -> Self {
+        let (stop, stopped) = mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            for frame in frames.iter().cycle().skip(1) {
+                if stopped.recv_timeout(SPINNER_INTERVAL) != Err(mpsc::RecvTimeoutError::Timeout) {
+                    break;
+                }
+                let drawn = term.clear_screen().and_then(|_| term.write_str(frame)).and_then(|_| term.flush());
+                if drawn.is_err() {
+                    break;
+                }
+            }
+        });
+        Spinner { stop: Some(stop), thread: Some(thread) }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        // disconnecting the channel stops the thread at its next frame
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Reads keys on a background thread, one at a time as they're requested, so the menu can watch
+/// for resizes while waiting for a key. Only reading on request means no key is taken from the
+/// terminal after the menu closes.
+struct KeyReader {
+    requests: mpsc::Sender<()>,
+    keys: mpsc::Receiver<io::Result<Key>>,
+}
+
+impl KeyReader {
+    /// Starts the reading thread, which stops once the reader is dropped.
+    fn start(term: Box<dyn TerminalOutput + Send>) -> Self {
+        let (requests, requested) = mpsc::channel::<()>();
+        let (sender, keys) = mpsc::channel();
+        std::thread::spawn(move || {
+            for () in requested {
+                if sender.send(term.read_key()).is_err() {
+                    break;
+                }
+            }
+        });
+        KeyReader { requests, keys }
+    }
+
+    /// Asks the thread to read the next key, sent on `keys`.
+    fn request(&self) {
+        let _ = self.requests.send(());
+    }
+}
+
+/// A terminal that only collects the text written to it, for `Menu::render_to_string`.
+struct StringTerm {
+    output: RefCell<String>,
+    size: (u16, u16),
+}
+
+impl TerminalOutput for StringTerm {
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.output.borrow_mut().push_str(s);
+        Ok(())
+    }
+
+    fn size(&self) -> (u16, u16) {
+        self.size
+    }
+
+    fn read_key(&self) -> io::Result<Key> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "no keys can be read"))
+    }
+}
+
+/// Pads `s` with spaces on the left until it fills `width` columns. `display_width` is the
+/// number of columns `s` already takes up, as returned by `text_width`.
+fn pad_left(s: String, display_width: usize, width: usize) -> String {
+    let padding = width.saturating_sub(display_width);
+    format!("{}{}", " ".repeat(padding), s)
+}
+
+/// Pads `s` with spaces on the right until it fills `width` columns. `display_width` is the
+/// number of columns `s` already takes up, as returned by `text_width`.
+fn pad_right(s: String, display_width: usize, width: usize) -> String {
+    let padding = width.saturating_sub(display_width);
+    format!("{}{}", s, " ".repeat(padding))
+}
+
+/// Number of terminal columns a string takes up when printed, ignoring ANSI escape sequences.
+/// Wide characters like CJK and emoji take up two columns, combining characters take up none.
+fn text_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in s.chars() {
+        if in_escape {
+            in_escape = !c.is_ascii_alphabetic();
+        } else if c == '\x1b' {
+            in_escape = true;
+        } else {
+            width += c.width().unwrap_or(0);
+        }
+    }
+    width
+}
+
+/// A short name for a key, as shown in key hints.
+fn key_name(key: &Key) -> String {
+    match key {
+        Key::ArrowUp => "↑".to_owned(),
+        Key::ArrowDown => "↓".to_owned(),
+        Key::ArrowLeft => "←".to_owned(),
+        Key::ArrowRight => "→".to_owned(),
+        Key::Enter => "Enter".to_owned(),
+        Key::Escape => "Esc".to_owned(),
+        Key::Backspace => "Backspace".to_owned(),
+        Key::Tab => "Tab".to_owned(),
+        Key::Home => "Home".to_owned(),
+        Key::End => "End".to_owned(),
+        Key::PageUp => "PgUp".to_owned(),
+        Key::PageDown => "PgDn".to_owned(),
+        Key::Char(' ') => "Space".to_owned(),
+        Key::Char(c) => c.to_string(),
+        key => format!("{:?}", key),
+    }
+}
+
+/// Finds the first case-insensitive occurrence of `query` in `s`, returning its byte range.
+fn find_match(s: &str, query: &str) -> Option<Range<usize>> {
+    let query: String = query.chars().flat_map(char::to_lowercase).collect();
+    for start in s.char_indices().map(|(i, _)| i).chain([s.len()]) {
+        let mut lowered = String::new();
+        let mut end = start;
+        for c in s[start..].chars() {
+            if lowered.len() >= query.len() {
+                break;
+            }
+            lowered.extend(c.to_lowercase());
+            end += c.len_utf8();
+        }
+        if lowered == query {
+            return Some(start..end);
+        }
+    }
+    None
+}
+
+/// Shortens `s` to at most `width` columns, replacing the cut off end with `…`. Combining
+/// characters stay attached to the character before them.
+fn truncate(s: &str, width: usize) -> String {
+    if text_width(s) <= width {
+        return s.to_owned();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if w > 0 && used + w + 1 > width {
+            break;
+        }
+        used += w;
+        out.push(c);
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+fn clamp(num: usize, min: usize, max: usize) -> usize {
+    let out = if num < min { min } else { num };
+    if out > max { max } else { out }
 }